@@ -0,0 +1,44 @@
+//! Tooling for the skills under `skills/`: reading their markdown and
+//! checking the Rust code they ship.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+pub mod lint;
+pub mod markdown;
+pub mod migrate;
+pub mod template;
+pub mod validate;
+
+/// Files under `path` whose extension is one of `extensions`, sorted so
+/// output is stable. A path that is not a directory is returned as is.
+pub fn collect_files(path: &Path, extensions: &[&str]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_into(path, extensions, &mut files)?;
+    Ok(files)
+}
+
+fn collect_into(path: &Path, extensions: &[&str], files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_into(&entry, extensions, files)?;
+        } else if entry
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext))
+        {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
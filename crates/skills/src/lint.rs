@@ -0,0 +1,398 @@
+//! Flags Dioxus APIs that were removed before 0.7.
+//!
+//! Rust sources are parsed with `syn` and walked as an AST. Macro bodies
+//! such as `rsx! { ... }` are not Rust syntax, so their tokens are scanned
+//! for the same patterns instead. Code that does not parse at all (a
+//! markdown fragment, say) falls back to the token scan for the whole input.
+
+use std::path::Path;
+use std::str::FromStr;
+use std::{fmt, fs, io};
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use syn::visit::{self, Visit};
+
+use crate::markdown::{code_blocks, CodeBlock};
+
+/// A removed API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    Scope,
+    CxProps,
+    RenderMacro,
+    CxRender,
+    UseState,
+    UseRef,
+    InlineProps,
+    HookCx,
+}
+
+impl Rule {
+    /// Short name shown next to each diagnostic.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Scope => "scope",
+            Rule::CxProps => "cx-props",
+            Rule::RenderMacro => "render-macro",
+            Rule::CxRender => "cx-render",
+            Rule::UseState => "use-state",
+            Rule::UseRef => "use-ref",
+            Rule::InlineProps => "inline-props",
+            Rule::HookCx => "hook-cx",
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            Rule::Scope => "`Scope` was removed in Dioxus 0.5; take props as function arguments",
+            Rule::CxProps => {
+                "`cx.props` was removed in Dioxus 0.5; read the prop argument directly"
+            }
+            Rule::RenderMacro => "`render!` was removed in Dioxus 0.5; use `rsx!`",
+            Rule::CxRender => {
+                "`cx.render` was removed in Dioxus 0.5; return `rsx! { ... }` directly"
+            }
+            Rule::UseState => "`use_state` was removed in Dioxus 0.5; use `use_signal`",
+            Rule::UseRef => "`use_ref` was removed in Dioxus 0.5; use `use_signal`",
+            Rule::InlineProps => "`#[inline_props]` was removed in Dioxus 0.5; use `#[component]`",
+            Rule::HookCx => "hooks no longer take `cx` since Dioxus 0.5; drop the argument",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One use of a removed API. `line` is 1-based and `column` is 1-based in
+/// characters, relative to the linted source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    fn at(rule: Rule, span: Span) -> Self {
+        let start = span.start();
+        Self {
+            rule,
+            line: start.line,
+            column: start.column + 1,
+        }
+    }
+}
+
+/// Lints a `.rs` file, or the ```rust blocks of a `.md` file. Other files
+/// have nothing to lint.
+pub fn lint_file(path: &Path) -> io::Result<Vec<Diagnostic>> {
+    let lint = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => lint_source,
+        Some("md") => lint_markdown,
+        _ => return Ok(Vec::new()),
+    };
+    Ok(lint(&fs::read_to_string(path)?))
+}
+
+/// Lints every ```rust block in `markdown`, including `ignore`d ones, with
+/// lines relative to the markdown document.
+pub fn lint_markdown(markdown: &str) -> Vec<Diagnostic> {
+    code_blocks(markdown)
+        .into_iter()
+        .filter(CodeBlock::is_rust)
+        .flat_map(|block| {
+            lint_source(&block.code)
+                .into_iter()
+                .map(move |mut diagnostic| {
+                    diagnostic.line += block.line - 1;
+                    diagnostic
+                })
+        })
+        .collect()
+}
+
+/// Lints Rust source, returning diagnostics in source order.
+pub fn lint_source(source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = match syn::parse_file(source) {
+        Ok(file) => {
+            let mut visitor = Visitor::default();
+            visitor.visit_file(&file);
+            visitor.diagnostics
+        }
+        Err(_) => match TokenStream::from_str(source) {
+            Ok(tokens) => scan_tokens(tokens),
+            Err(_) => Vec::new(),
+        },
+    };
+
+    diagnostics.sort_by_key(|d| (d.line, d.column, d.rule));
+    diagnostics.dedup();
+    diagnostics
+}
+
+#[derive(Default)]
+struct Visitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor {
+    fn report(&mut self, rule: Rule, span: Span) {
+        self.diagnostics.push(Diagnostic::at(rule, span));
+    }
+}
+
+impl<'ast> Visit<'ast> for Visitor {
+    fn visit_path_segment(&mut self, segment: &'ast syn::PathSegment) {
+        match segment.ident.to_string().as_str() {
+            "Scope" => self.report(Rule::Scope, segment.ident.span()),
+            "use_state" => self.report(Rule::UseState, segment.ident.span()),
+            "use_ref" => self.report(Rule::UseRef, segment.ident.span()),
+            _ => {}
+        }
+        visit::visit_path_segment(self, segment);
+    }
+
+    fn visit_use_name(&mut self, name: &'ast syn::UseName) {
+        match name.ident.to_string().as_str() {
+            "Scope" => self.report(Rule::Scope, name.ident.span()),
+            "use_state" => self.report(Rule::UseState, name.ident.span()),
+            "use_ref" => self.report(Rule::UseRef, name.ident.span()),
+            _ => {}
+        }
+    }
+
+    fn visit_attribute(&mut self, attr: &'ast syn::Attribute) {
+        if attr.path().is_ident("inline_props") {
+            self.report(Rule::InlineProps, attr.path().segments[0].ident.span());
+        }
+        visit::visit_attribute(self, attr);
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if is_cx(&expr.base) {
+            if let syn::Member::Named(member) = &expr.member {
+                if member == "props" {
+                    self.report(Rule::CxProps, member.span());
+                }
+            }
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if is_cx(&call.receiver) && call.method == "render" {
+            self.report(Rule::CxRender, call.method.span());
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = &*call.func {
+            let name = &func.path.segments.last().unwrap().ident;
+            if is_hook(&name.to_string()) && call.args.first().is_some_and(is_cx) {
+                self.report(Rule::HookCx, name.span());
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.is_ident("render") {
+            self.report(Rule::RenderMacro, mac.path.segments[0].ident.span());
+        }
+        self.diagnostics.extend(scan_tokens(mac.tokens.clone()));
+        visit::visit_macro(self, mac);
+    }
+}
+
+fn is_cx(expr: &syn::Expr) -> bool {
+    matches!(expr, syn::Expr::Path(path) if path.qself.is_none() && path.path.is_ident("cx"))
+}
+
+fn is_hook(name: &str) -> bool {
+    name.starts_with("use_")
+}
+
+/// Token-level version of [`Visitor`], for macro bodies and unparsable code.
+fn scan_tokens(tokens: TokenStream) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    scan_into(tokens, &mut diagnostics);
+    diagnostics
+}
+
+fn scan_into(tokens: TokenStream, diagnostics: &mut Vec<Diagnostic>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1);
+        match token {
+            TokenTree::Group(group) => {
+                // Format strings such as "{cx.props.title}" hide field accesses in literals.
+                scan_into(group.stream(), diagnostics);
+            }
+            TokenTree::Literal(literal) => {
+                let text = literal.to_string();
+                if text.starts_with('"') && text.contains("cx.props") {
+                    diagnostics.push(Diagnostic::at(Rule::CxProps, literal.span()));
+                }
+            }
+            TokenTree::Punct(_) => {}
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                let rule = match name.as_str() {
+                    "Scope" => Some(Rule::Scope),
+                    "use_state" => Some(Rule::UseState),
+                    "use_ref" => Some(Rule::UseRef),
+                    "inline_props" => Some(Rule::InlineProps),
+                    "render" if is_punct(next, '!') => Some(Rule::RenderMacro),
+                    "cx" if is_punct(next, '.') => match tokens.get(i + 2) {
+                        Some(TokenTree::Ident(member)) if member == "props" => {
+                            diagnostics.push(Diagnostic::at(Rule::CxProps, member.span()));
+                            None
+                        }
+                        Some(TokenTree::Ident(member)) if member == "render" => {
+                            diagnostics.push(Diagnostic::at(Rule::CxRender, member.span()));
+                            None
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(rule) = rule {
+                    diagnostics.push(Diagnostic::at(rule, ident.span()));
+                }
+                if is_hook(&name) && takes_cx(next) {
+                    diagnostics.push(Diagnostic::at(Rule::HookCx, ident.span()));
+                }
+            }
+        }
+    }
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == ch)
+}
+
+/// Whether `token` is an argument list whose first argument is `cx`.
+fn takes_cx(token: Option<&TokenTree>) -> bool {
+    let Some(TokenTree::Group(group)) = token else {
+        return false;
+    };
+    if group.delimiter() != Delimiter::Parenthesis {
+        return false;
+    }
+
+    let mut args = group.stream().into_iter();
+    matches!(args.next(), Some(TokenTree::Ident(ident)) if ident == "cx")
+        && (args
+            .next()
+            .is_none_or(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ',')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(source: &str) -> Vec<Rule> {
+        lint_source(source).into_iter().map(|d| d.rule).collect()
+    }
+
+    #[test]
+    fn scope_argument() {
+        assert_eq!(
+            rules("fn App(cx: Scope) -> Element { todo!() }"),
+            [Rule::Scope]
+        );
+    }
+
+    #[test]
+    fn cx_props_in_code_and_format_strings() {
+        let source = r#"
+fn App(props: P) -> Element {
+    let title = cx.props.title.clone();
+    rsx! { h2 { "{cx.props.title}" } }
+}
+"#;
+        assert_eq!(rules(source), [Rule::CxProps, Rule::CxProps]);
+    }
+
+    #[test]
+    fn render_macro_and_cx_render() {
+        let source = "fn A() -> Element { render! { div {} } }\nfn B() -> Element { cx.render(rsx! { div {} }) }";
+        assert_eq!(rules(source), [Rule::RenderMacro, Rule::CxRender]);
+    }
+
+    #[test]
+    fn removed_hooks() {
+        let source = "use dioxus::hooks::use_ref;\nfn A() { let a = use_state(cx, || 0); }";
+        assert_eq!(rules(source), [Rule::UseRef, Rule::UseState, Rule::HookCx]);
+    }
+
+    #[test]
+    fn hooks_taking_cx_inside_macros() {
+        let source = "fn A() -> Element { rsx! { {use_signal(cx, || 0)} {use_context(cx)} } }";
+        assert_eq!(rules(source), [Rule::HookCx, Rule::HookCx]);
+    }
+
+    #[test]
+    fn hook_with_other_first_argument_is_fine() {
+        assert!(rules("fn A() { let a = use_signal(cx_value, || 0); }").is_empty());
+    }
+
+    #[test]
+    fn inline_props_attribute() {
+        assert_eq!(
+            rules("#[inline_props]\nfn A(cx: Scope) {}"),
+            [Rule::InlineProps, Rule::Scope]
+        );
+    }
+
+    #[test]
+    fn fragments_fall_back_to_tokens() {
+        let diagnostics = lint_source("let x = use_state(cx, || 0);\nrender! { div {} }");
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            (diagnostics[2].rule, diagnostics[2].line),
+            (Rule::RenderMacro, 2)
+        );
+    }
+
+    #[test]
+    fn positions_are_one_based() {
+        let diagnostics = lint_source("fn main() {\n    render! {}\n}");
+
+        assert_eq!(
+            diagnostics,
+            [Diagnostic {
+                rule: Rule::RenderMacro,
+                line: 2,
+                column: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn markdown_lines_are_document_relative() {
+        let doc = "# Hooks\n\n```rust,ignore\nlet a = use_state(cx, || 0);\n```\n\n```bash\nrender!\n```\n";
+        let diagnostics = lint_markdown(doc);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.line == 4));
+    }
+
+    #[test]
+    fn current_api_is_clean() {
+        let source = r#"
+#[component]
+fn App(title: String) -> Element {
+    let mut count = use_signal(|| 0);
+    rsx! { h1 { "{title} {count}" } button { onclick: move |_| count += 1 } }
+}
+"#;
+        assert!(rules(source).is_empty());
+    }
+}
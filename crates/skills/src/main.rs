@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{fs, io};
+
+use clap::{Parser, Subcommand};
+use skills::collect_files;
+use skills::lint::{lint_file, lint_source};
+use skills::migrate::migrate_source;
+use skills::template::{instantiate, Name};
+use skills::validate::validate_skill;
+
+/// Tooling for the skills in this repository.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report Dioxus APIs removed before 0.7 in `.rs` files and in the
+    /// ```rust blocks of `.md` files.
+    Lint {
+        /// Files or directories to lint.
+        #[arg(default_value = "skills")]
+        paths: Vec<PathBuf>,
+    },
+    /// Rewrite pre-0.5 `Scope` components in `.rs` files into 0.7 idioms,
+    /// in place.
+    Migrate {
+        /// Report files that would change without writing them.
+        #[arg(long)]
+        check: bool,
+        /// Files or directories to migrate.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Copy a template into a crate under a new name, e.g.
+    /// `new component --name UserCard`.
+    New {
+        /// Template to copy: a `<template>.rs` with a `<template>.toml`
+        /// manifest.
+        template: String,
+        /// Name of the copy, in any case: `UserCard` or `user-card`.
+        #[arg(long)]
+        name: String,
+        /// Crate to write into.
+        #[arg(long = "crate", default_value = ".")]
+        krate: PathBuf,
+        /// Directory holding the templates and their manifests.
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../../skills/dioxus-fullstack/assets/templates"))]
+        templates: PathBuf,
+        /// Replace the file if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check each `SKILL.md`: its frontmatter, and that the files its body
+    /// refers to exist.
+    Validate {
+        /// Fail when a body is estimated at more than this many tokens.
+        #[arg(long)]
+        max_tokens: Option<usize>,
+        /// Skill directories, or directories containing skills.
+        #[arg(default_value = "skills")]
+        paths: Vec<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Lint { paths } => lint(&paths),
+        Command::Migrate { check, paths } => migrate(&paths, check),
+        Command::New {
+            template,
+            name,
+            krate,
+            templates,
+            force,
+        } => new(&templates, &template, &name, &krate, force),
+        Command::Validate { max_tokens, paths } => validate(&paths, max_tokens),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn lint(paths: &[PathBuf]) -> io::Result<ExitCode> {
+    let mut files = Vec::new();
+    for path in paths {
+        files.extend(collect_files(path, &["rs", "md"])?);
+    }
+
+    let mut count = 0;
+    for file in &files {
+        let diagnostics = lint_file(file)?;
+        for d in &diagnostics {
+            println!(
+                "{}:{}:{}: {}: {}",
+                file.display(),
+                d.line,
+                d.column,
+                d.rule,
+                d.rule.message()
+            );
+        }
+        count += diagnostics.len();
+    }
+
+    if count == 0 {
+        return Ok(ExitCode::SUCCESS);
+    }
+    eprintln!(
+        "{count} use(s) of removed Dioxus APIs in {} file(s) checked",
+        files.len()
+    );
+    Ok(ExitCode::FAILURE)
+}
+
+fn migrate(paths: &[PathBuf], check: bool) -> io::Result<ExitCode> {
+    let mut files = Vec::new();
+    for path in paths {
+        files.extend(collect_files(path, &["rs"])?);
+    }
+
+    let mut changed = 0;
+    for file in &files {
+        let source = fs::read_to_string(file)?;
+        let migrated = migrate_source(&source).map_err(|err| {
+            let start = err.span().start();
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{}:{}:{}: {err}",
+                    file.display(),
+                    start.line,
+                    start.column + 1
+                ),
+            )
+        })?;
+        if migrated == source {
+            continue;
+        }
+
+        changed += 1;
+        println!("{}", file.display());
+        if !check {
+            fs::write(file, &migrated)?;
+        }
+
+        // What the codemod cannot rewrite, such as `use_state`, is left for a
+        // person to port.
+        for d in lint_source(&migrated) {
+            eprintln!(
+                "warning: {}:{}:{}: {}: {}",
+                file.display(),
+                d.line,
+                d.column,
+                d.rule,
+                d.rule.message()
+            );
+        }
+    }
+
+    if check && changed > 0 {
+        eprintln!("{changed} of {} file(s) need migrating", files.len());
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn validate(paths: &[PathBuf], max_tokens: Option<usize>) -> io::Result<ExitCode> {
+    let mut skills = Vec::new();
+    for path in paths {
+        let found: Vec<_> = collect_files(path, &["md"])?
+            .into_iter()
+            .filter(|file| file.file_name().is_some_and(|name| name == "SKILL.md"))
+            .collect();
+        if found.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no SKILL.md under {}", path.display()),
+            ));
+        }
+        skills.extend(found);
+    }
+
+    let mut count = 0;
+    for skill in &skills {
+        let problems = validate_skill(skill, max_tokens)?;
+        for p in &problems {
+            println!("{}:{}: {}: {}", skill.display(), p.line, p.check, p.message);
+        }
+        count += problems.len();
+    }
+
+    if count == 0 {
+        return Ok(ExitCode::SUCCESS);
+    }
+    eprintln!("{count} problem(s) in {} skill(s) checked", skills.len());
+    Ok(ExitCode::FAILURE)
+}
+
+fn new(
+    templates: &Path,
+    template: &str,
+    name: &str,
+    krate: &Path,
+    force: bool,
+) -> io::Result<ExitCode> {
+    let name = Name::parse(name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("`{name}` is not a valid name; use letters, digits, `-` or `_`"),
+        )
+    })?;
+
+    let created = instantiate(templates, template, &name, krate, force)?;
+    println!("created {}", created.file.display());
+    println!("updated {}", created.mod_rs.display());
+    if created.new_mod_rs {
+        let dir = created
+            .mod_rs
+            .parent()
+            .and_then(Path::file_name)
+            .unwrap_or_default();
+        eprintln!(
+            "note: declare `mod {};` in the crate root to use it",
+            dir.to_string_lossy()
+        );
+    }
+    for module in &created.missing {
+        eprintln!(
+            "warning: it uses `crate::{module}`; copy `{module}.rs` from {} to src/ and declare `mod {module};`",
+            templates.display()
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
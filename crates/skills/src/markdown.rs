@@ -0,0 +1,190 @@
+//! Fenced code blocks in skill markdown.
+//!
+//! Info strings follow the rustdoc convention: the first token is the
+//! language and the rest are attributes, separated by commas or spaces
+//! (```` ```rust,ignore ````).
+
+/// A fenced code block and where it starts in its document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Language from the info string, empty when the fence has none.
+    pub lang: String,
+    /// Remaining info string tokens, e.g. `ignore`.
+    pub attrs: Vec<String>,
+    /// The block contents, without the fences.
+    pub code: String,
+    /// 1-based line of the first line of `code` in the document.
+    pub line: usize,
+}
+
+impl CodeBlock {
+    pub fn is_rust(&self) -> bool {
+        self.lang == "rust"
+    }
+
+    /// Whether the block opted out of compilation with `ignore`.
+    pub fn is_ignored(&self) -> bool {
+        self.has_attr("ignore")
+    }
+
+    pub fn has_attr(&self, attr: &str) -> bool {
+        self.attrs.iter().any(|a| a == attr)
+    }
+}
+
+/// Returns every fenced code block in `markdown`, in document order.
+///
+/// An unterminated fence runs to the end of the document, as in CommonMark.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(Fence, CodeBlock)> = None;
+
+    for (index, line) in markdown.lines().enumerate() {
+        match &mut open {
+            None => {
+                if let Some((fence, info)) = Fence::parse(line) {
+                    let mut tokens = info
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_string);
+                    let block = CodeBlock {
+                        lang: tokens.next().unwrap_or_default(),
+                        attrs: tokens.collect(),
+                        code: String::new(),
+                        line: index + 2,
+                    };
+                    open = Some((fence, block));
+                }
+            }
+            Some((fence, block)) => {
+                if fence.closes(line) {
+                    blocks.push(open.take().unwrap().1);
+                } else {
+                    block.code.push_str(line);
+                    block.code.push('\n');
+                }
+            }
+        }
+    }
+
+    blocks.extend(open.map(|(_, block)| block));
+    blocks
+}
+
+/// Lines of `markdown` outside fenced code blocks, with their 1-based line
+/// numbers. Fence lines themselves are skipped too.
+pub fn text_lines(markdown: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut open: Option<Fence> = None;
+
+    for (index, line) in markdown.lines().enumerate() {
+        match &open {
+            None => match Fence::parse(line) {
+                Some((fence, _)) => open = Some(fence),
+                None => lines.push((index + 1, line)),
+            },
+            Some(fence) => {
+                if fence.closes(line) {
+                    open = None;
+                }
+            }
+        }
+    }
+    lines
+}
+
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    /// Parses an opening fence, returning it with its info string.
+    fn parse(line: &str) -> Option<(Self, &str)> {
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            return None;
+        }
+
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = trimmed.chars().take_while(|c| *c == marker).count();
+        if len < 3 {
+            return None;
+        }
+
+        let info = trimmed[len..].trim();
+        if marker == '`' && info.contains('`') {
+            return None;
+        }
+        Some((Self { marker, len }, info))
+    }
+
+    fn closes(&self, line: &str) -> bool {
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            return false;
+        }
+
+        let len = trimmed.chars().take_while(|c| *c == self.marker).count();
+        len >= self.len && trimmed[len..].trim().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_blocks_with_language_and_line() {
+        let doc = "# Title\n\n```rust\nfn main() {}\n```\n\ntext\n\n```bash\ndx serve\n```\n";
+        let blocks = code_blocks(doc);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].lang, "rust");
+        assert_eq!(blocks[0].code, "fn main() {}\n");
+        assert_eq!(blocks[0].line, 4);
+        assert_eq!(blocks[1].lang, "bash");
+        assert_eq!(blocks[1].line, 10);
+    }
+
+    #[test]
+    fn parses_attributes() {
+        let blocks = code_blocks("```rust,ignore\nx\n```\n```rust no_run ignore\ny\n```\n");
+
+        assert!(blocks[0].is_rust());
+        assert!(blocks[0].is_ignored());
+        assert_eq!(blocks[1].attrs, ["no_run", "ignore"]);
+    }
+
+    #[test]
+    fn untagged_fence_has_empty_lang() {
+        let blocks = code_blocks("```\nsrc/\n```\n");
+
+        assert_eq!(blocks[0].lang, "");
+        assert!(!blocks[0].is_rust());
+    }
+
+    #[test]
+    fn longer_fence_contains_shorter_one() {
+        let doc = "````markdown\n```rust\nfn f() {}\n```\n````\n";
+        let blocks = code_blocks(doc);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].lang, "markdown");
+        assert_eq!(blocks[0].code, "```rust\nfn f() {}\n```\n");
+    }
+
+    #[test]
+    fn text_lines_skip_fenced_code() {
+        let doc = "intro\n```rust\nfn f() {}\n```\noutro\n";
+
+        assert_eq!(text_lines(doc), [(1, "intro"), (5, "outro")]);
+    }
+
+    #[test]
+    fn unterminated_fence_runs_to_end() {
+        let blocks = code_blocks("```rust\nfn f() {}\n");
+
+        assert_eq!(blocks[0].code, "fn f() {}\n");
+    }
+}
@@ -0,0 +1,494 @@
+//! Rewrites pre-0.5 Dioxus components into 0.7 idioms.
+//!
+//! The source is parsed with `syn` to find what needs to change, and the
+//! changes are spliced back into the original text, so formatting and
+//! comments outside the rewritten spans survive. New signatures are printed
+//! with `prettyplease`.
+//!
+//! Rewrites:
+//! - `fn X(cx: Scope<XProps>)` with `XProps` defined in the same file takes
+//!   the struct's fields as arguments and the struct is removed, because
+//!   `#[component]` generates it. Any other `Scope<P>` becomes `props: P`.
+//! - `fn X(cx: Scope, id: u32)` drops `cx`, and `cx: &ScopeState` hook
+//!   arguments are dropped.
+//! - Components get `#[component]`, replacing `#[inline_props]`.
+//! - `cx.props.x` becomes `x` (or `props.x`), also in format strings.
+//! - `render!` becomes `rsx!` and `cx.render(x)` becomes `x`.
+//! - `use_*(cx, ...)` hooks lose their `cx` argument.
+//! - `use dioxus_router::prelude::*` is removed; the router is in
+//!   `dioxus::prelude` with the `router` feature.
+//!
+//! Anything else, such as `use_state`, is left for [`crate::lint`] to report.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{parse_quote, FnArg, GenericArgument, Item, ItemFn, ItemStruct, PathArguments, Type};
+
+/// Migrates one Rust source file. Returns the input unchanged when there is
+/// nothing to do.
+pub fn migrate_source(source: &str) -> syn::Result<String> {
+    let file = syn::parse_file(source)?;
+
+    let structs = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some((item.ident.to_string(), item)),
+            _ => None,
+        })
+        .collect();
+
+    let mut migrator = Migrator {
+        source,
+        structs,
+        edits: Vec::new(),
+        props: PropsAccess::Inline,
+    };
+    migrator.visit_file(&file);
+
+    Ok(apply(source, migrator.edits))
+}
+
+/// How `cx.props.x` is rewritten inside the current component.
+#[derive(Clone, Copy)]
+enum PropsAccess {
+    /// Props are arguments: `x`.
+    Inline,
+    /// Props are a struct argument: `props.x`.
+    Field,
+}
+
+impl PropsAccess {
+    fn prefix(self) -> &'static str {
+        match self {
+            PropsAccess::Inline => "",
+            PropsAccess::Field => "props.",
+        }
+    }
+}
+
+struct Migrator<'a> {
+    source: &'a str,
+    structs: HashMap<String, &'a ItemStruct>,
+    edits: Vec<(Range<usize>, String)>,
+    props: PropsAccess,
+}
+
+impl Migrator<'_> {
+    fn edit(&mut self, range: Range<usize>, text: impl Into<String>) {
+        self.edits.push((range, text.into()));
+    }
+
+    /// Removes an item's lines, and the blank line after it when the item
+    /// was separated from the previous one by a blank line too.
+    fn remove_item(&mut self, span: Span) {
+        let range = span.byte_range();
+        let rest = &self.source[range.end..];
+        let trailing = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        let mut end = range.end + trailing;
+        if self.source[end..].starts_with('\n') {
+            end += 1;
+        }
+        let before = &self.source[..range.start];
+        if (before.is_empty() || before.ends_with("\n\n")) && self.source[end..].starts_with('\n') {
+            end += 1;
+        }
+        self.edit(range.start..end, "");
+    }
+
+    fn migrate_component(&mut self, item: &ItemFn) {
+        let Some(FnArg::Typed(cx)) = item.sig.inputs.first() else {
+            return;
+        };
+
+        let mut sig = item.sig.clone();
+        let rest: Vec<FnArg> = sig.inputs.iter().skip(1).cloned().collect();
+        sig.inputs = Punctuated::new();
+
+        if is_scope_state(&cx.ty) {
+            // A custom hook: `fn use_x(cx: &ScopeState)`.
+            sig.inputs.extend(rest);
+            self.replace_signature(item, &sig);
+            return;
+        }
+
+        let Some(props) = scope_props(&cx.ty) else {
+            return;
+        };
+
+        match props {
+            None => {
+                // `Scope` or inline props: `fn X(cx: Scope, id: u32)`.
+                sig.inputs.extend(rest);
+                self.props = PropsAccess::Inline;
+            }
+            Some(props) => match self.inlinable_props(&item.sig.ident.to_string(), &props) {
+                Some(props) => {
+                    for field in &props.fields {
+                        let attrs = &field.attrs;
+                        let ident = &field.ident;
+                        let ty = &field.ty;
+                        sig.inputs.push(parse_quote!(#(#attrs)* #ident: #ty));
+                    }
+                    self.remove_item(props.span());
+                    self.props = PropsAccess::Inline;
+                }
+                None => {
+                    sig.inputs.push(parse_quote!(props: #props));
+                    self.props = PropsAccess::Field;
+                }
+            },
+        }
+
+        self.replace_signature(item, &sig);
+
+        let has_component = item
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("component"));
+        for attr in &item.attrs {
+            if attr.path().is_ident("inline_props") {
+                let text = if has_component { "" } else { "#[component]" };
+                self.edit(attr.span().byte_range(), text);
+                return;
+            }
+        }
+        if !has_component {
+            let start = item.span().byte_range().start;
+            let indent = self.indent_at(start).to_string();
+            self.edit(start..start, format!("#[component]\n{indent}"));
+        }
+    }
+
+    /// The props struct for `component`, if its fields can become arguments:
+    /// it is defined in this file, named `{component}Props` (the name
+    /// `#[component]` generates) and has named fields and no generics.
+    fn inlinable_props(&self, component: &str, props: &Type) -> Option<&ItemStruct> {
+        let Type::Path(path) = props else {
+            return None;
+        };
+        let name = path.path.get_ident()?.to_string();
+        if name != format!("{component}Props") {
+            return None;
+        }
+
+        let item = *self.structs.get(&name)?;
+        let named = matches!(item.fields, syn::Fields::Named(_));
+        (named && item.generics.params.is_empty()).then_some(item)
+    }
+
+    fn replace_signature(&mut self, item: &ItemFn, sig: &syn::Signature) {
+        let printed = prettyplease::unparse(&syn::File {
+            shebang: None,
+            attrs: Vec::new(),
+            items: vec![Item::Fn(ItemFn {
+                attrs: Vec::new(),
+                vis: syn::Visibility::Inherited,
+                sig: sig.clone(),
+                block: Box::new(parse_quote!({})),
+            })],
+        });
+        let printed = printed.trim_end().trim_end_matches("{}").trim_end();
+
+        let range = item.sig.span().byte_range();
+        let indent = self.indent_at(range.start).to_string();
+        self.edit(range, printed.replace('\n', &format!("\n{indent}")));
+    }
+
+    fn indent_at(&self, offset: usize) -> &str {
+        let line_start = self.source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = &self.source[line_start..offset];
+        &line[..line.len() - line.trim_start().len()]
+    }
+
+    /// Token-level rewrites for macro bodies, which `syn` does not parse.
+    fn migrate_tokens(&mut self, tokens: TokenStream) {
+        let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => self.migrate_tokens(group.stream()),
+                TokenTree::Literal(literal) => {
+                    let text = literal.to_string();
+                    if text.starts_with('"') && text.contains("cx.props.") {
+                        let migrated = text.replace("cx.props.", self.props.prefix());
+                        self.edit(literal.span().byte_range(), migrated);
+                    }
+                }
+                TokenTree::Punct(_) => {}
+                TokenTree::Ident(ident) => {
+                    let next = tokens.get(i + 1);
+                    if ident == "render" && is_punct(next, '!') {
+                        self.edit(ident.span().byte_range(), "rsx");
+                    } else if ident == "cx" && is_punct(next, '.') {
+                        self.migrate_cx_tokens(ident.span(), &tokens[i + 2..]);
+                    } else if ident.to_string().starts_with("use_") {
+                        if let Some(TokenTree::Group(args)) = next {
+                            if args.delimiter() == Delimiter::Parenthesis {
+                                self.drop_cx_token(args.stream());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// `cx.props.x` and `cx.render(...)` in a token stream; `rest` follows `cx.`.
+    fn migrate_cx_tokens(&mut self, cx: Span, rest: &[TokenTree]) {
+        let start = cx.byte_range().start;
+        match rest {
+            [TokenTree::Ident(props), TokenTree::Punct(dot), TokenTree::Ident(field), ..]
+                if props == "props" && dot.as_char() == '.' =>
+            {
+                self.edit(start..field.span().byte_range().start, self.props.prefix());
+            }
+            [TokenTree::Ident(render), TokenTree::Group(args), ..]
+                if render == "render" && args.delimiter() == Delimiter::Parenthesis =>
+            {
+                self.edit(start..args.span_open().byte_range().end, "");
+                self.edit(args.span_close().byte_range(), "");
+            }
+            _ => {}
+        }
+    }
+
+    /// Drops a leading `cx` argument from a hook's argument tokens.
+    fn drop_cx_token(&mut self, args: TokenStream) {
+        let args: Vec<TokenTree> = args.into_iter().collect();
+        let [TokenTree::Ident(cx), rest @ ..] = args.as_slice() else {
+            return;
+        };
+        if cx != "cx" {
+            return;
+        }
+
+        let start = cx.span().byte_range().start;
+        match rest {
+            [] => self.edit(cx.span().byte_range(), ""),
+            [TokenTree::Punct(comma), next, ..] if comma.as_char() == ',' => {
+                self.edit(start..next.span().byte_range().start, "")
+            }
+            [TokenTree::Punct(comma)] if comma.as_char() == ',' => {
+                self.edit(start..comma.span().byte_range().end, "")
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Migrator<'_> {
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        if let syn::UseTree::Path(path) = &item.tree {
+            if path.ident == "dioxus_router" {
+                self.remove_item(item.span());
+            }
+        }
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        let outer = self.props;
+        self.migrate_component(item);
+        visit::visit_item_fn(self, item);
+        self.props = outer;
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if let syn::Expr::Field(inner) = &*expr.base {
+            if is_cx(&inner.base) && matches!(&inner.member, syn::Member::Named(m) if m == "props")
+            {
+                let start = inner.span().byte_range().start;
+                let end = expr.member.span().byte_range().start;
+                self.edit(start..end, self.props.prefix());
+                return;
+            }
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if is_cx(&call.receiver) && call.method == "render" && call.args.len() == 1 {
+            let arg = call.args[0].span().byte_range();
+            let whole = call.span().byte_range();
+            self.edit(whole.start..arg.start, "");
+            self.edit(arg.end..whole.end, "");
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(func) = &*call.func {
+            let name = func.path.segments.last().unwrap().ident.to_string();
+            if name.starts_with("use_") && call.args.first().is_some_and(is_cx) {
+                let start = call.args[0].span().byte_range().start;
+                let end = match call.args.iter().nth(1) {
+                    Some(next) => next.span().byte_range().start,
+                    None => call.args.span().byte_range().end,
+                };
+                self.edit(start..end, "");
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.is_ident("render") {
+            self.edit(mac.path.span().byte_range(), "rsx");
+        }
+        self.migrate_tokens(mac.tokens.clone());
+    }
+}
+
+/// `Some(None)` for a bare `Scope`, `Some(Some(P))` for `Scope<P>`, `None`
+/// when `ty` is not a `Scope`.
+fn scope_props(ty: &Type) -> Option<Option<Type>> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Scope" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Some(None);
+    };
+    Some(args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }))
+}
+
+fn is_scope_state(ty: &Type) -> bool {
+    matches!(ty, Type::Reference(r) if matches!(&*r.elem, Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "ScopeState")))
+}
+
+fn is_cx(expr: &syn::Expr) -> bool {
+    matches!(expr, syn::Expr::Path(path) if path.qself.is_none() && path.path.is_ident("cx"))
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == ch)
+}
+
+/// Applies non-overlapping edits to `source`.
+fn apply(source: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+    edits.dedup();
+
+    let mut out = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for (range, text) in edits {
+        if range.start < cursor {
+            continue;
+        }
+        out.push_str(&source[cursor..range.start]);
+        out.push_str(&text);
+        cursor = range.end;
+    }
+    out.push_str(&source[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrate(source: &str) -> String {
+        migrate_source(source).unwrap()
+    }
+
+    #[test]
+    fn bare_scope_is_dropped() {
+        assert_eq!(
+            migrate("#[component]\nfn App(cx: Scope) -> Element {\n    render! { div {} }\n}\n"),
+            "#[component]\nfn App() -> Element {\n    rsx! { div {} }\n}\n"
+        );
+    }
+
+    #[test]
+    fn inline_props_keep_their_arguments() {
+        assert_eq!(
+            migrate("#[inline_props]\nfn Post(cx: Scope, id: u32) -> Element {\n    todo!()\n}\n"),
+            "#[component]\nfn Post(id: u32) -> Element {\n    todo!()\n}\n"
+        );
+    }
+
+    #[test]
+    fn missing_component_attribute_is_added() {
+        assert_eq!(
+            migrate("pub fn App(cx: Scope) -> Element {\n    todo!()\n}\n"),
+            "#[component]\npub fn App() -> Element {\n    todo!()\n}\n"
+        );
+    }
+
+    #[test]
+    fn matching_props_struct_becomes_arguments() {
+        let source = r#"#[derive(Props, PartialEq)]
+pub struct CardProps {
+    /// Shown in the header.
+    #[props(default)]
+    pub title: String,
+}
+
+#[component]
+fn Card(cx: Scope<CardProps>) -> Element {
+    let title = cx.props.title.clone();
+    render! { h2 { "{cx.props.title}" } }
+}
+"#;
+        let expected = r#"#[component]
+fn Card(
+    /// Shown in the header.
+    #[props(default)]
+    title: String,
+) -> Element {
+    let title = title.clone();
+    rsx! { h2 { "{title}" } }
+}
+"#;
+        assert_eq!(migrate(source), expected);
+    }
+
+    #[test]
+    fn other_props_struct_is_passed_whole() {
+        let source = "#[component]\nfn Card(cx: Scope<Shared>) -> Element {\n    render! { \"{cx.props.title}\" }\n}\n";
+        let expected =
+            "#[component]\nfn Card(props: Shared) -> Element {\n    rsx! { \"{props.title}\" }\n}\n";
+        assert_eq!(migrate(source), expected);
+    }
+
+    #[test]
+    fn cx_render_is_unwrapped() {
+        assert_eq!(
+            migrate("fn App(cx: Scope) -> Element {\n    cx.render(rsx! { div {} })\n}\n"),
+            "#[component]\nfn App() -> Element {\n    rsx! { div {} }\n}\n"
+        );
+    }
+
+    #[test]
+    fn hooks_lose_cx_in_code_and_macros() {
+        let source = "fn use_thing(cx: &ScopeState) -> i32 {\n    let a = use_signal(cx, || 0);\n    let b = use_context(cx);\n    rsx! { {use_hook(cx, || 1)} }\n}\n";
+        let expected = "fn use_thing() -> i32 {\n    let a = use_signal(|| 0);\n    let b = use_context();\n    rsx! { {use_hook(|| 1)} }\n}\n";
+        assert_eq!(migrate(source), expected);
+    }
+
+    #[test]
+    fn router_prelude_import_is_removed() {
+        assert_eq!(
+            migrate("use dioxus::prelude::*;\nuse dioxus_router::prelude::*;\n\nfn f() {}\n"),
+            "use dioxus::prelude::*;\n\nfn f() {}\n"
+        );
+    }
+
+    #[test]
+    fn current_code_is_untouched() {
+        let source = "#[component]\nfn App(title: String) -> Element {\n    let n = use_signal(|| 0);\n    rsx! { \"{title} {n}\" }\n}\n";
+        assert_eq!(migrate(source), source);
+    }
+}
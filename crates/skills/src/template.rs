@@ -0,0 +1,479 @@
+//! Instantiates a skill template under a new name.
+//!
+//! Each template `assets/templates/<template>.rs` that can be instantiated
+//! has a manifest `<template>.toml` next to it:
+//!
+//! ```toml
+//! # Where the file goes: `src/components/` or `src/pages/`.
+//! destination = "components"
+//! # Top-level items to leave out of the copy.
+//! skip = ["ActionCard"]
+//!
+//! # Words in the template and what they become. `{pascal}`, `{camel}`,
+//! # `{snake}`, `{kebab}` and `{upper}` are the new name in that case.
+//! [placeholders]
+//! Component = "{pascal}"
+//! ComponentProps = "{pascal}Props"
+//! '"component"' = '"{kebab}"'
+//! ```
+//!
+//! Placeholders only match whole words, so `Component` does not touch
+//! `#[component]` or `ComponentProps`.
+//!
+//! A template that uses other templates as modules of the crate lists them,
+//! as in `requires = ["error", "hooks"]` for `crate::error` and
+//! `crate::hooks`. The copy only builds once the crate has them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use syn::spanned::Spanned;
+
+/// A template's `<template>.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub destination: Destination,
+    #[serde(default)]
+    pub skip: Vec<String>,
+    /// Templates the copy uses as `crate::<module>`.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    pub placeholders: BTreeMap<String, String>,
+}
+
+/// The `src/` subdirectory a template is written to, following the project
+/// structure in `SKILL.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Destination {
+    Components,
+    Pages,
+}
+
+impl Destination {
+    pub fn dir(self) -> &'static str {
+        match self {
+            Destination::Components => "components",
+            Destination::Pages => "pages",
+        }
+    }
+}
+
+/// A name split into lowercase words, so it can be printed in any case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Name {
+    words: Vec<String>,
+}
+
+impl Name {
+    /// Splits `UserCard`, `userCard`, `user-card`, `user_card` or `HTTPClient`
+    /// into words. Returns `None` unless the name is ASCII letters and digits
+    /// (plus separators) and starts with a letter.
+    pub fn parse(name: &str) -> Option<Self> {
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' '));
+        if !valid {
+            return None;
+        }
+
+        let chars: Vec<char> = name.chars().collect();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if !c.is_ascii_alphanumeric() {
+                words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+                continue;
+            }
+
+            // A new word starts at `aB`, `1B`, and at the last capital of an
+            // acronym followed by lowercase: `HTTPClient` is `HTTP` + `Client`.
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let boundary = c.is_ascii_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_ascii_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_ascii_uppercase() && next.is_some_and(char::is_ascii_lowercase))
+                });
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c.to_ascii_lowercase());
+        }
+        words.extend((!word.is_empty()).then_some(word));
+
+        Some(Self { words })
+    }
+
+    /// `UserCard`
+    pub fn pascal(&self) -> String {
+        self.words.iter().map(|w| capitalize(w)).collect()
+    }
+
+    /// `userCard`
+    pub fn camel(&self) -> String {
+        let pascal = self.pascal();
+        let mut chars = pascal.chars();
+        chars
+            .next()
+            .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+            .unwrap_or_default()
+    }
+
+    /// `user_card`
+    pub fn snake(&self) -> String {
+        self.words.join("_")
+    }
+
+    /// `user-card`
+    pub fn kebab(&self) -> String {
+        self.words.join("-")
+    }
+
+    /// `USER_CARD`
+    pub fn upper(&self) -> String {
+        self.snake().to_ascii_uppercase()
+    }
+
+    fn case(&self, case: &str) -> Option<String> {
+        Some(match case {
+            "pascal" => self.pascal(),
+            "camel" => self.camel(),
+            "snake" => self.snake(),
+            "kebab" => self.kebab(),
+            "upper" => self.upper(),
+            _ => return None,
+        })
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// What [`instantiate`] wrote.
+#[derive(Debug)]
+pub struct Created {
+    /// The rendered template.
+    pub file: PathBuf,
+    /// The `mod.rs` that now declares it.
+    pub mod_rs: PathBuf,
+    /// Whether `mod.rs` did not exist before, in which case the crate root
+    /// still has to declare the directory's module.
+    pub new_mod_rs: bool,
+    /// The manifest's `requires` the crate has no module for yet.
+    pub missing: Vec<String>,
+}
+
+/// Loads `<template>.rs` and `<template>.toml` from `templates`.
+pub fn load(templates: &Path, template: &str) -> io::Result<(String, Manifest)> {
+    let source = fs::read_to_string(templates.join(format!("{template}.rs")))?;
+    let manifest_path = templates.join(format!("{template}.toml"));
+    let manifest = fs::read_to_string(&manifest_path).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => io::Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "`{template}` has no manifest at {}",
+                manifest_path.display()
+            ),
+        ),
+        _ => err,
+    })?;
+    let manifest: Manifest = toml::from_str(&manifest)
+        .map_err(|err| invalid(format!("{}: {err}", manifest_path.display())))?;
+    if let Some(module) = manifest
+        .requires
+        .iter()
+        .find(|module| !templates.join(format!("{module}.rs")).exists())
+    {
+        return Err(invalid(format!(
+            "{}: requires `{module}`, which is not a template",
+            manifest_path.display()
+        )));
+    }
+    Ok((source, manifest))
+}
+
+/// Renders `template` from `templates` as `name` into `krate`'s
+/// `src/<destination>/`, and declares it in that directory's `mod.rs`.
+/// An existing file is only replaced with `force`.
+pub fn instantiate(
+    templates: &Path,
+    template: &str,
+    name: &Name,
+    krate: &Path,
+    force: bool,
+) -> io::Result<Created> {
+    let (source, manifest) = load(templates, template)?;
+    let rendered = render(&source, &manifest, name)?;
+
+    let module = name.snake();
+    if syn::parse_str::<syn::Ident>(&module).is_err() {
+        return Err(invalid(format!("`{module}` is not a valid module name")));
+    }
+
+    let dir = krate.join("src").join(manifest.destination.dir());
+    let file = dir.join(format!("{module}.rs"));
+    if file.exists() && !force {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists; pass --force to replace it",
+                file.display()
+            ),
+        ));
+    }
+
+    let mod_rs = dir.join("mod.rs");
+    let new_mod_rs = !mod_rs.exists();
+    let declarations = if new_mod_rs {
+        String::new()
+    } else {
+        fs::read_to_string(&mod_rs)?
+    };
+
+    fs::create_dir_all(&dir)?;
+    fs::write(&file, rendered)?;
+    fs::write(&mod_rs, declare_module(&declarations, &module))?;
+
+    let src = krate.join("src");
+    let missing = manifest
+        .requires
+        .into_iter()
+        .filter(|module| {
+            !src.join(format!("{module}.rs")).exists() && !src.join(module).join("mod.rs").exists()
+        })
+        .collect();
+
+    Ok(Created {
+        file,
+        mod_rs,
+        new_mod_rs,
+        missing,
+    })
+}
+
+/// Renders a template as `name`: drops its `// Dioxus 0.7 ... Template`
+/// header line and the manifest's `skip` items, then substitutes the
+/// placeholders.
+pub fn render(source: &str, manifest: &Manifest, name: &Name) -> io::Result<String> {
+    let source = match source.split_once('\n') {
+        Some((first, rest)) if first.starts_with("// ") && first.ends_with(" Template") => rest,
+        _ => source,
+    };
+    let source = skip_items(source, &manifest.skip)?;
+
+    let mut replacements = Vec::new();
+    for (word, value) in &manifest.placeholders {
+        replacements.push((word.as_str(), substitute(value, name)?));
+    }
+    // Longest first, so `ComponentProps` wins over a `Component` prefix.
+    replacements.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+
+    Ok(replace_words(&source, &replacements))
+}
+
+fn skip_items(source: &str, skip: &[String]) -> io::Result<String> {
+    if skip.is_empty() {
+        return Ok(source.to_string());
+    }
+
+    let file = syn::parse_file(source).map_err(|err| invalid(format!("template: {err}")))?;
+    let mut ranges = Vec::new();
+    for name in skip {
+        let item = file
+            .items
+            .iter()
+            .find(|item| item_name(item).is_some_and(|ident| ident == name))
+            .ok_or_else(|| invalid(format!("template has no item `{name}` to skip")))?;
+
+        let range = item.span().byte_range();
+        // Take the line break and one blank line after the item with it.
+        let newlines = source[range.end..]
+            .chars()
+            .take(2)
+            .take_while(|c| *c == '\n')
+            .count();
+        ranges.push(range.start..range.end + newlines);
+    }
+    ranges.sort_by_key(|range| range.start);
+
+    let mut out = String::new();
+    let mut cursor = 0;
+    for range in ranges {
+        out.push_str(&source[cursor..range.start]);
+        cursor = range.end;
+    }
+    out.push_str(&source[cursor..]);
+
+    // Skipping the last item leaves a blank line at the end of the file.
+    let trimmed = out.trim_end_matches('\n').len();
+    out.truncate(trimmed);
+    out.push('\n');
+    Ok(out)
+}
+
+fn item_name(item: &syn::Item) -> Option<&syn::Ident> {
+    match item {
+        syn::Item::Const(item) => Some(&item.ident),
+        syn::Item::Enum(item) => Some(&item.ident),
+        syn::Item::Fn(item) => Some(&item.sig.ident),
+        syn::Item::Static(item) => Some(&item.ident),
+        syn::Item::Struct(item) => Some(&item.ident),
+        syn::Item::Type(item) => Some(&item.ident),
+        _ => None,
+    }
+}
+
+/// Expands `{pascal}` and the other cases of `name` in a placeholder value.
+fn substitute(value: &str, name: &Name) -> io::Result<String> {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| invalid(format!("unclosed `{{` in placeholder `{value}`")))?;
+        let case = &rest[start + 1..start + end];
+        let text = name.case(case).ok_or_else(|| {
+            invalid(format!(
+                "unknown case `{{{case}}}` in placeholder `{value}`"
+            ))
+        })?;
+
+        out.push_str(&rest[..start]);
+        out.push_str(&text);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Replaces each word in `replacements` where it is not part of a longer
+/// identifier.
+fn replace_words(source: &str, replacements: &[(&str, String)]) -> String {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &source[i..];
+        let at_boundary = !source[..i].ends_with(is_ident);
+        let found = replacements.iter().find(|(word, _)| {
+            at_boundary && rest.starts_with(word) && !rest[word.len()..].starts_with(is_ident)
+        });
+
+        match found {
+            Some((word, value)) => {
+                out.push_str(value);
+                i += word.len();
+            }
+            None => {
+                let c = rest.chars().next().unwrap();
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    out
+}
+
+/// Adds `mod <module>;` and a glob re-export to a `mod.rs`, after its other
+/// declarations. Already declared modules are left alone.
+pub fn declare_module(mod_rs: &str, module: &str) -> String {
+    let declared = mod_rs.lines().any(|line| {
+        let line = line.trim_start().trim_start_matches("pub ");
+        line == format!("mod {module};")
+    });
+    if declared {
+        return mod_rs.to_string();
+    }
+
+    let mut out = mod_rs.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(&format!("mod {module};\npub use {module}::*;\n"));
+    out
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(name: &str) -> Vec<String> {
+        Name::parse(name).unwrap().words
+    }
+
+    #[test]
+    fn names_split_into_words() {
+        assert_eq!(words("UserCard"), ["user", "card"]);
+        assert_eq!(words("userCard"), ["user", "card"]);
+        assert_eq!(words("user-card"), ["user", "card"]);
+        assert_eq!(words("user_card"), ["user", "card"]);
+        assert_eq!(words("HTTPClient"), ["http", "client"]);
+        assert_eq!(words("Page2Header"), ["page2", "header"]);
+        assert_eq!(Name::parse("2Fast"), None);
+        assert_eq!(Name::parse("user.card"), None);
+    }
+
+    #[test]
+    fn cases() {
+        let name = Name::parse("UserCard").unwrap();
+
+        assert_eq!(name.pascal(), "UserCard");
+        assert_eq!(name.camel(), "userCard");
+        assert_eq!(name.snake(), "user_card");
+        assert_eq!(name.kebab(), "user-card");
+        assert_eq!(name.upper(), "USER_CARD");
+    }
+
+    #[test]
+    fn placeholders_match_whole_words() {
+        let name = Name::parse("UserCard").unwrap();
+        let replacements = [
+            ("Component", substitute("{pascal}", &name).unwrap()),
+            ("\"component\"", substitute("\"{kebab}\"", &name).unwrap()),
+        ];
+
+        assert_eq!(
+            replace_words(
+                "#[component]\nfn Component(p: ComponentProps) { \"component\" }",
+                &replacements
+            ),
+            "#[component]\nfn UserCard(p: ComponentProps) { \"user-card\" }"
+        );
+    }
+
+    #[test]
+    fn unknown_case_is_an_error() {
+        let name = Name::parse("UserCard").unwrap();
+
+        assert!(substitute("{title}", &name).is_err());
+        assert!(substitute("{pascal", &name).is_err());
+    }
+
+    #[test]
+    fn modules_are_declared_once() {
+        let mod_rs = declare_module("mod header;\npub use header::*;", "user_card");
+
+        assert_eq!(
+            mod_rs,
+            "mod header;\npub use header::*;\nmod user_card;\npub use user_card::*;\n"
+        );
+        assert_eq!(declare_module(&mod_rs, "user_card"), mod_rs);
+    }
+}
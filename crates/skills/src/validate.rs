@@ -0,0 +1,449 @@
+//! Checks a skill's `SKILL.md`: its YAML frontmatter and the files its body
+//! points at.
+//!
+//! A skill is a directory holding `SKILL.md`, optionally with `references/`,
+//! `assets/` and `scripts/` next to it. The body refers to those files by
+//! relative path, either as a markdown link or in an inline code span such
+//! as `` `assets/templates/route.rs` ``.
+
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use serde::Deserialize;
+
+use crate::markdown::text_lines;
+
+/// Longest `name` allowed in the frontmatter.
+pub const MAX_NAME_LEN: usize = 64;
+/// Longest `description` allowed in the frontmatter, in characters.
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+
+/// Top-level directories a skill's body can refer to in inline code.
+const SKILL_DIRS: [&str; 3] = ["references/", "assets/", "scripts/"];
+
+/// A failed check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Check {
+    Frontmatter,
+    Name,
+    Description,
+    MissingPath,
+    TokenBudget,
+}
+
+impl Check {
+    /// Short name shown next to each problem.
+    pub fn name(self) -> &'static str {
+        match self {
+            Check::Frontmatter => "frontmatter",
+            Check::Name => "name",
+            Check::Description => "description",
+            Check::MissingPath => "missing-path",
+            Check::TokenBudget => "token-budget",
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One problem in a `SKILL.md`. `line` is 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub check: Check,
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(check: Check, line: usize, message: impl Into<String>) -> Self {
+        Self {
+            check,
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// The frontmatter fields this crate checks. Other keys are allowed.
+#[derive(Debug, Deserialize)]
+struct Frontmatter {
+    name: String,
+    description: String,
+}
+
+/// Validates the skill whose `SKILL.md` is at `path`. With `max_tokens`, the
+/// body's estimated token count must not exceed it.
+pub fn validate_skill(path: &Path, max_tokens: Option<usize>) -> io::Result<Vec<Problem>> {
+    let source = fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let dir_name = fs::canonicalize(dir)?
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(validate_source(
+        &source,
+        &dir_name,
+        max_tokens,
+        |relative| exists(dir, relative),
+    ))
+}
+
+/// Validates `SKILL.md` contents for a skill in a directory named `dir_name`.
+/// `exists` reports whether a path relative to the skill exists.
+pub fn validate_source(
+    source: &str,
+    dir_name: &str,
+    max_tokens: Option<usize>,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<Problem> {
+    let Some((yaml, body, body_line)) = split_frontmatter(source) else {
+        return vec![Problem::new(
+            Check::Frontmatter,
+            1,
+            "SKILL.md must start with `---` frontmatter closed by a `---` line",
+        )];
+    };
+
+    let mut problems = Vec::new();
+    match serde_yaml::from_str::<Frontmatter>(yaml) {
+        Ok(frontmatter) => {
+            check_name(
+                &frontmatter.name,
+                dir_name,
+                key_line(yaml, "name"),
+                &mut problems,
+            );
+            let line = key_line(yaml, "description");
+            check_description(&frontmatter.description, line, &mut problems);
+        }
+        Err(err) => {
+            // Frontmatter starts on line 2, after the opening `---`.
+            let line = err.location().map_or(1, |l| l.line() + 1);
+            problems.push(Problem::new(Check::Frontmatter, line, err.to_string()));
+        }
+    }
+
+    for (line, path) in relative_paths(body) {
+        if !exists(&path) {
+            problems.push(Problem::new(
+                Check::MissingPath,
+                body_line + line - 1,
+                format!("`{path}` does not exist"),
+            ));
+        }
+    }
+
+    if let Some(max) = max_tokens {
+        let tokens = estimate_tokens(body);
+        if tokens > max {
+            problems.push(Problem::new(
+                Check::TokenBudget,
+                body_line,
+                format!("body is about {tokens} tokens, over the budget of {max}"),
+            ));
+        }
+    }
+
+    problems
+}
+
+/// Splits `source` into frontmatter YAML and body, with the body's 1-based
+/// starting line.
+fn split_frontmatter(source: &str) -> Option<(&str, &str, usize)> {
+    let rest = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for (index, line) in rest.split_inclusive('\n').enumerate() {
+        if line.trim_end() == "---" {
+            let body = &rest[offset + line.len()..];
+            return Some((&rest[..offset], body, index + 3));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// The line of the top-level `key` in the frontmatter, counting the opening
+/// `---` as line 1. serde_yaml does not report where values are.
+fn key_line(yaml: &str, key: &str) -> usize {
+    yaml.lines()
+        .position(|line| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+        .map_or(1, |index| index + 2)
+}
+
+fn check_name(name: &str, dir_name: &str, line: usize, problems: &mut Vec<Problem>) {
+    if !is_kebab_case(name) {
+        problems.push(Problem::new(
+            Check::Name,
+            line,
+            format!("`{name}` must be kebab-case: lowercase letters, digits and single hyphens"),
+        ));
+    }
+    if name.len() > MAX_NAME_LEN {
+        problems.push(Problem::new(
+            Check::Name,
+            line,
+            format!(
+                "name is {} characters, over the limit of {MAX_NAME_LEN}",
+                name.len()
+            ),
+        ));
+    }
+    if name != dir_name {
+        problems.push(Problem::new(
+            Check::Name,
+            line,
+            format!("`{name}` does not match the skill directory `{dir_name}`"),
+        ));
+    }
+}
+
+fn check_description(description: &str, line: usize, problems: &mut Vec<Problem>) {
+    let len = description.chars().count();
+    if description.trim().is_empty() {
+        problems.push(Problem::new(
+            Check::Description,
+            line,
+            "description is empty",
+        ));
+    } else if len > MAX_DESCRIPTION_LEN {
+        problems.push(Problem::new(
+            Check::Description,
+            line,
+            format!("description is {len} characters, over the limit of {MAX_DESCRIPTION_LEN}"),
+        ));
+    }
+}
+
+fn is_kebab_case(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// Relative paths referenced from `body` outside fenced code blocks, with
+/// their 1-based line in `body`: every relative link target, and inline code
+/// that starts with one of the skill's directories.
+fn relative_paths(body: &str) -> Vec<(usize, String)> {
+    let mut paths = Vec::new();
+    for (line_number, line) in text_lines(body) {
+        for target in link_targets(line) {
+            if is_relative_link(target) {
+                let path = target.split('#').next().unwrap_or(target);
+                paths.push((line_number, path.to_string()));
+            }
+        }
+        for code in inline_code(line) {
+            if SKILL_DIRS.iter().any(|dir| code.starts_with(dir)) && !code.contains(' ') {
+                paths.push((line_number, code.to_string()));
+            }
+        }
+    }
+    paths
+}
+
+/// Targets of `[text](target)` links in `line`.
+fn link_targets(line: &str) -> impl Iterator<Item = &str> {
+    line.match_indices("](").filter_map(move |(start, _)| {
+        let rest = &line[start + 2..];
+        let end = rest.find(')')?;
+        Some(rest[..end].split_whitespace().next().unwrap_or_default())
+    })
+}
+
+fn is_relative_link(target: &str) -> bool {
+    !target.is_empty()
+        && !target.contains("://")
+        && !target.starts_with(['#', '/'])
+        && !target.starts_with("mailto:")
+}
+
+/// Contents of single-backtick code spans in `line`.
+fn inline_code(line: &str) -> impl Iterator<Item = &str> {
+    line.split('`').skip(1).step_by(2)
+}
+
+/// Whether `relative` exists under `dir`. A `*` in the last path component
+/// matches any run of characters, and the pattern must match at least one
+/// file.
+fn exists(dir: &Path, relative: &str) -> bool {
+    let Some((parent, pattern)) = relative.rsplit_once('/').filter(|_| relative.contains('*'))
+    else {
+        return dir.join(relative).exists();
+    };
+
+    let Ok(entries) = fs::read_dir(dir.join(parent)) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| glob_match(pattern, name))
+    })
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// A rough token count for English prose and code: about four characters
+/// per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(source: &str) -> Vec<Problem> {
+        validate_source(source, "my-skill", None, |path| path == "references/api.md")
+    }
+
+    fn checks(source: &str) -> Vec<Check> {
+        validate(source).into_iter().map(|p| p.check).collect()
+    }
+
+    #[test]
+    fn valid_skill() {
+        let source = "---\nname: my-skill\ndescription: Does things.\nlicense: MIT\n---\n\n# My skill\n\nSee [the API](references/api.md) and `references/api.md`.\n";
+        assert_eq!(validate(source), []);
+    }
+
+    #[test]
+    fn missing_or_unclosed_frontmatter() {
+        assert_eq!(checks("# My skill\n"), [Check::Frontmatter]);
+        assert_eq!(
+            checks("---\nname: my-skill\n# My skill\n"),
+            [Check::Frontmatter]
+        );
+    }
+
+    #[test]
+    fn frontmatter_that_does_not_parse() {
+        let problems = validate("---\nname: my-skill\ndescription: [unclosed\n---\n");
+
+        assert_eq!(problems[0].check, Check::Frontmatter);
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn missing_description() {
+        assert_eq!(checks("---\nname: my-skill\n---\n"), [Check::Frontmatter]);
+    }
+
+    #[test]
+    fn name_must_be_kebab_case_and_match_directory() {
+        assert_eq!(
+            checks("---\nname: My_Skill\ndescription: x\n---\n"),
+            [Check::Name, Check::Name]
+        );
+        assert_eq!(
+            checks("---\nname: other-skill\ndescription: x\n---\n"),
+            [Check::Name]
+        );
+        assert!(!is_kebab_case("my--skill"));
+        assert!(!is_kebab_case("-skill"));
+        assert!(is_kebab_case("skill-2"));
+    }
+
+    #[test]
+    fn problems_point_at_their_key() {
+        let problems = validate("---\ndescription: \"\"\nname: my-skill\n---\n");
+
+        assert_eq!(problems[0].check, Check::Description);
+        assert_eq!(problems[0].line, 2);
+    }
+
+    #[test]
+    fn description_length() {
+        let long = "x".repeat(MAX_DESCRIPTION_LEN + 1);
+        assert_eq!(
+            checks(&format!("---\nname: my-skill\ndescription: {long}\n---\n")),
+            [Check::Description]
+        );
+        assert_eq!(
+            checks("---\nname: my-skill\ndescription: \"\"\n---\n"),
+            [Check::Description]
+        );
+    }
+
+    #[test]
+    fn missing_paths_are_reported_on_their_line() {
+        let source = "---\nname: my-skill\ndescription: x\n---\n\n[guide](references/guide.md#setup)\n\n```\nassets/not-checked.rs\n```\n`assets/templates/app.rs` and `src/main.rs`\n[docs](https://dioxuslabs.com) [top](#top)\n";
+        let problems = validate(source);
+
+        assert_eq!(
+            problems,
+            [
+                Problem::new(
+                    Check::MissingPath,
+                    6,
+                    "`references/guide.md` does not exist"
+                ),
+                Problem::new(
+                    Check::MissingPath,
+                    11,
+                    "`assets/templates/app.rs` does not exist"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn token_budget() {
+        let source = format!(
+            "---\nname: my-skill\ndescription: x\n---\n{}",
+            "word ".repeat(100)
+        );
+
+        assert_eq!(
+            validate_source(&source, "my-skill", Some(125), |_| true),
+            []
+        );
+        let problems = validate_source(&source, "my-skill", Some(100), |_| true);
+        assert_eq!(problems[0].check, Check::TokenBudget);
+        assert_eq!(problems[0].line, 5);
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_match("*.rs", "route.rs"));
+        assert!(glob_match("r*e*.rs", "route.rs"));
+        assert!(!glob_match("*.rs", "route.md"));
+        assert!(!glob_match("*.rs.rs", "a.rs"));
+        assert!(glob_match("route.rs", "route.rs"));
+    }
+}
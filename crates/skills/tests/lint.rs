@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use skills::collect_files;
+use skills::lint::{lint_file, Rule};
+
+fn repo_root() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))
+}
+
+fn rules(fixture: &str) -> Vec<Rule> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture);
+    lint_file(&path)
+        .unwrap()
+        .into_iter()
+        .map(|d| d.rule)
+        .collect()
+}
+
+#[test]
+fn skills_use_no_removed_apis() {
+    let mut failures = Vec::new();
+    for file in collect_files(&repo_root().join("skills"), &["rs", "md"]).unwrap() {
+        for d in lint_file(&file).unwrap() {
+            failures.push(format!(
+                "{}:{}:{}: {}",
+                file.display(),
+                d.line,
+                d.column,
+                d.rule
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "removed Dioxus APIs in skills:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn scope_component_template() {
+    assert_eq!(
+        rules("scope/component.rs"),
+        [Rule::Scope, Rule::RenderMacro, Rule::CxProps, Rule::CxProps]
+    );
+}
+
+#[test]
+fn scope_route_template() {
+    let rules = rules("scope/route.rs");
+
+    assert_eq!(rules.iter().filter(|r| **r == Rule::Scope).count(), 5);
+    assert_eq!(rules.iter().filter(|r| **r == Rule::RenderMacro).count(), 5);
+}
+
+#[test]
+fn scope_server_function_template() {
+    assert_eq!(
+        rules("scope/server_function.rs"),
+        [Rule::Scope, Rule::HookCx, Rule::HookCx, Rule::RenderMacro]
+    );
+}
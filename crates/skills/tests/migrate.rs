@@ -0,0 +1,40 @@
+use std::fs;
+use std::path::Path;
+
+use skills::lint::lint_source;
+use skills::migrate::migrate_source;
+
+/// Migrates a pre-0.7 template fixture and checks nothing removed is left.
+fn migrate(fixture: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture);
+    let migrated = migrate_source(&fs::read_to_string(path).unwrap()).unwrap();
+
+    assert_eq!(lint_source(&migrated), [], "{migrated}");
+    syn::parse_file(&migrated).unwrap();
+    migrated
+}
+
+#[test]
+fn scope_component_template() {
+    insta::assert_snapshot!(migrate("scope/component.rs"));
+}
+
+#[test]
+fn scope_route_template() {
+    insta::assert_snapshot!(migrate("scope/route.rs"));
+}
+
+#[test]
+fn scope_server_function_template() {
+    insta::assert_snapshot!(migrate("scope/server_function.rs"));
+}
+
+#[test]
+fn migrated_templates_are_stable() {
+    for fixture in ["component.rs", "route.rs", "server_function.rs"] {
+        let migrated = migrate(&format!("scope/{fixture}"));
+        assert_eq!(migrate_source(&migrated).unwrap(), migrated, "{fixture}");
+    }
+}
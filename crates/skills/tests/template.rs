@@ -0,0 +1,96 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use skills::lint::lint_source;
+use skills::template::{instantiate, Name};
+
+fn templates() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../skills/dioxus-fullstack/assets/templates")
+}
+
+/// Instantiates `template` as `name` in an empty crate and returns the new
+/// file, after checking it is valid, current Rust.
+fn instantiate_in(krate: &Path, template: &str, name: &str) -> String {
+    let name = Name::parse(name).unwrap();
+    let created = instantiate(&templates(), template, &name, krate, false).unwrap();
+
+    let source = fs::read_to_string(&created.file).unwrap();
+    syn::parse_file(&source).unwrap();
+    assert_eq!(lint_source(&source), [], "{source}");
+    source
+}
+
+#[test]
+fn component_as_user_card() {
+    let krate = tempfile::tempdir().unwrap();
+    let source = instantiate_in(krate.path(), "component", "UserCard");
+
+    assert!(krate.path().join("src/components/user_card.rs").exists());
+    insta::assert_snapshot!(source);
+}
+
+#[test]
+fn server_function_as_user_search() {
+    let krate = tempfile::tempdir().unwrap();
+    let source = instantiate_in(krate.path(), "server_function", "user-search");
+
+    assert!(krate.path().join("src/pages/user_search.rs").exists());
+    insta::assert_snapshot!(source);
+}
+
+#[test]
+fn reports_required_modules_the_crate_lacks() {
+    let krate = tempfile::tempdir().unwrap();
+    let name = Name::parse("UserSearch").unwrap();
+
+    let created = instantiate(&templates(), "server_function", &name, krate.path(), false).unwrap();
+    assert_eq!(created.missing, ["error", "hooks"]);
+
+    let src = krate.path().join("src");
+    fs::write(src.join("error.rs"), "").unwrap();
+    fs::create_dir(src.join("hooks")).unwrap();
+    fs::write(src.join("hooks/mod.rs"), "").unwrap();
+    let created = instantiate(&templates(), "server_function", &name, krate.path(), true).unwrap();
+    assert!(created.missing.is_empty(), "{:?}", created.missing);
+}
+
+#[test]
+fn mod_rs_declares_each_copy() {
+    let krate = tempfile::tempdir().unwrap();
+    let components = krate.path().join("src/components");
+    fs::create_dir_all(&components).unwrap();
+    fs::write(
+        components.join("mod.rs"),
+        "mod header;\npub use header::*;\n",
+    )
+    .unwrap();
+
+    instantiate_in(krate.path(), "component", "UserCard");
+    instantiate_in(krate.path(), "component", "TeamCard");
+
+    assert_eq!(
+        fs::read_to_string(components.join("mod.rs")).unwrap(),
+        "mod header;\npub use header::*;\nmod user_card;\npub use user_card::*;\nmod team_card;\npub use team_card::*;\n"
+    );
+}
+
+#[test]
+fn existing_file_needs_force() {
+    let krate = tempfile::tempdir().unwrap();
+    let name = Name::parse("UserCard").unwrap();
+    instantiate(&templates(), "component", &name, krate.path(), false).unwrap();
+
+    let err = instantiate(&templates(), "component", &name, krate.path(), false).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    instantiate(&templates(), "component", &name, krate.path(), true).unwrap();
+}
+
+#[test]
+fn template_without_manifest() {
+    let krate = tempfile::tempdir().unwrap();
+    let name = Name::parse("Shell").unwrap();
+
+    let err = instantiate(&templates(), "route", &name, krate.path(), false).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
@@ -0,0 +1,34 @@
+use std::path::Path;
+
+use skills::collect_files;
+use skills::validate::validate_skill;
+
+#[test]
+fn shipped_skills_are_valid() {
+    let skills = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../skills"));
+    let manifests: Vec<_> = collect_files(skills, &["md"])
+        .unwrap()
+        .into_iter()
+        .filter(|file| file.ends_with("SKILL.md"))
+        .collect();
+    assert!(!manifests.is_empty());
+
+    let mut failures = Vec::new();
+    for manifest in &manifests {
+        for p in validate_skill(manifest, None).unwrap() {
+            failures.push(format!(
+                "{}:{}: {}: {}",
+                manifest.display(),
+                p.line,
+                p.check,
+                p.message
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "invalid skills:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,72 @@
+//! Writes every ```rust block in the skill's markdown to its own module file
+//! under `OUT_DIR`, plus a `snippets.rs` index that `lib.rs` includes.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use skills::markdown::code_blocks;
+
+fn main() -> io::Result<()> {
+    let skill = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../skills/dioxus-fullstack");
+    let out = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+
+    let mut docs = Vec::new();
+    markdown_files(&skill, &mut docs)?;
+    docs.sort();
+
+    let mut index = String::new();
+    for doc in docs {
+        println!("cargo:rerun-if-changed={}", doc.display());
+        let name = doc
+            .strip_prefix(&skill)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/");
+        let text = fs::read_to_string(&doc)?;
+
+        for block in code_blocks(&text) {
+            if !block.is_rust() || block.is_ignored() {
+                continue;
+            }
+
+            let module = module_name(&name, block.line);
+            let path = out.join(format!("{module}.rs"));
+            fs::write(&path, &block.code)?;
+
+            writeln!(index, "#[doc = \"`{name}:{}`\"]", block.line).unwrap();
+            writeln!(index, "#[path = {:?}]", path.display().to_string()).unwrap();
+            writeln!(index, "pub mod {module};").unwrap();
+        }
+    }
+
+    fs::write(out.join("snippets.rs"), index)
+}
+
+fn markdown_files(dir: &Path, docs: &mut Vec<PathBuf>) -> io::Result<()> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            markdown_files(&path, docs)?;
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            docs.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// `references/cheatsheet.md` at line 30 becomes `references_cheatsheet_md_l30`.
+fn module_name(doc: &str, line: usize) -> String {
+    let stem: String = doc
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{stem}_l{line}")
+}
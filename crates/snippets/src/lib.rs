@@ -0,0 +1,22 @@
+//! Compiles every ```rust block in the dioxus-fullstack skill's markdown
+//! against Dioxus 0.7. Each block becomes its own module, named after the
+//! file and the line the code starts on: an error at line 3 of
+//! `skill_md_l120.rs` is line 122 of `SKILL.md`.
+//!
+//! Blocks that are deliberately incomplete opt out with ```` ```rust,ignore ````.
+//! Each block must bring its own imports, so it still compiles once copied
+//! into a project. Projects are laid out from the templates, so blocks may
+//! import template modules as `crate::<module>`. As with the templates, check
+//! both feature sets:
+//!
+//! ```text
+//! cargo check -p dioxus-fullstack-snippets --features web
+//! cargo check -p dioxus-fullstack-snippets --features server
+//! ```
+
+// Examples define items they never use.
+#![allow(dead_code)]
+
+pub use dioxus_fullstack_templates::{error, form, hooks, server_function};
+
+include!(concat!(env!("OUT_DIR"), "/snippets.rs"));
@@ -0,0 +1,51 @@
+//! Writes two files to `OUT_DIR` for `lib.rs` to include:
+//!
+//! - the `main.rs` template without its `mod` declarations: in this crate
+//!   the templates are already modules of the library, and declaring them
+//!   again would build each one twice;
+//! - `server_function.rs` as `skills new server_function --name UserSearch`
+//!   writes it, so the copy is built against the modules it requires.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use skills::template::{load, render, Name};
+
+fn main() -> io::Result<()> {
+    let templates = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../skills/dioxus-fullstack/assets/templates");
+    let out = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    println!("cargo:rerun-if-changed={}", templates.display());
+
+    let text = fs::read_to_string(templates.join("main.rs"))?;
+    fs::write(out.join("main.rs"), without_modules(&text))?;
+
+    let (source, manifest) = load(&templates, "server_function")?;
+    let name = Name::parse("UserSearch").expect("a valid name");
+    fs::write(
+        out.join("user_search.rs"),
+        render(&source, &manifest, &name)?,
+    )
+}
+
+/// `text` with every `mod name;` line, and the attributes right above it,
+/// blanked out. Blank lines rather than none keep error line numbers the
+/// template's own.
+fn without_modules(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut attributes = Vec::new();
+    for index in 0..lines.len() {
+        let line = lines[index].trim();
+        if line.starts_with("#[") {
+            attributes.push(index);
+            continue;
+        }
+        if line.starts_with("mod ") && line.ends_with(';') {
+            for attribute in attributes.iter().copied().chain([index]) {
+                lines[attribute] = "";
+            }
+        }
+        attributes.clear();
+    }
+    lines.join("\n") + "\n"
+}
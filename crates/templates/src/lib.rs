@@ -0,0 +1,76 @@
+//! Compiles every file under `skills/dioxus-fullstack/assets/templates` as a
+//! module, so a template that drifts from the Dioxus 0.7 API breaks the build.
+//!
+//! Check both sides of the fullstack split separately:
+//!
+//! ```text
+//! cargo check -p dioxus-fullstack-templates --features web
+//! cargo check -p dioxus-fullstack-templates --features server
+//! ```
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/admin.rs"]
+pub mod admin;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/auth.rs"]
+pub mod auth;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/boundary.rs"]
+pub mod boundary;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/chat.rs"]
+pub mod chat;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/component.rs"]
+pub mod component;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/crud.rs"]
+pub mod crud;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/error.rs"]
+pub mod error;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/form.rs"]
+pub mod form;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/hooks.rs"]
+pub mod hooks;
+
+// A binary's `main` and what only it uses look unused from a library. The
+// build script drops its `mod` declarations: here the templates are the
+// modules above and below.
+#[allow(dead_code)]
+mod main {
+    include!(concat!(env!("OUT_DIR"), "/main.rs"));
+}
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/route.rs"]
+pub mod route;
+
+#[cfg(feature = "server")]
+#[path = "../../../skills/dioxus-fullstack/assets/templates/server.rs"]
+pub mod server;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/server_function.rs"]
+pub mod server_function;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/ssg.rs"]
+pub mod ssg;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/store.rs"]
+pub mod store;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/stream.rs"]
+pub mod stream;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/streaming.rs"]
+pub mod streaming;
+
+#[path = "../../../skills/dioxus-fullstack/assets/templates/upload.rs"]
+pub mod upload;
+
+/// `server_function.rs` as `skills new server_function --name UserSearch`
+/// copies it into a crate, next to the `error` and `hooks` modules its
+/// manifest requires.
+pub mod user_search {
+    include!(concat!(env!("OUT_DIR"), "/user_search.rs"));
+}
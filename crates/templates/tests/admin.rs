@@ -0,0 +1,89 @@
+//! Calls the admin server functions over HTTP directly, the way anyone can
+//! without going through `AdminGuard`, and checks they enforce the role
+//! themselves.
+#![cfg(feature = "server")]
+
+mod common;
+
+use common::{spawn_server, Client, Server};
+use dioxus_fullstack_templates::auth::{Role, User};
+use reqwest::StatusCode;
+use serde_json::json;
+
+/// A signed-in client for a new account. The first admin has to be made in
+/// the database: only admins can hand out the role over the API.
+async fn sign_up(server: &Server, email: &str, role: Role) -> Client {
+    let client = Client::new(server);
+    assert_eq!(
+        client.register(email, "correct horse").await,
+        StatusCode::OK
+    );
+    sqlx::query("UPDATE users SET role = ? WHERE email = ?")
+        .bind(role)
+        .bind(email)
+        .execute(&server.db)
+        .await
+        .unwrap();
+    client
+}
+
+#[tokio::test]
+async fn signed_out_calls_are_unauthorized() {
+    let server = spawn_server().await;
+    let user = sign_up(&server, "ada@example.com", Role::User).await;
+    let id = user.current_user().await.unwrap().id;
+    let client = Client::new(&server);
+
+    assert_eq!(
+        client.get("/api/admin/users").await.status(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        client
+            .post("/api/admin/role", json!({ "id": id, "role": "admin" }))
+            .await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+#[tokio::test]
+async fn users_are_forbidden() {
+    let server = spawn_server().await;
+    let user = sign_up(&server, "ada@example.com", Role::User).await;
+    let id = user.current_user().await.unwrap().id;
+
+    assert_eq!(
+        user.get("/api/admin/users").await.status(),
+        StatusCode::FORBIDDEN
+    );
+    // Not even to promote themselves.
+    assert_eq!(
+        user.post("/api/admin/role", json!({ "id": id, "role": "admin" }))
+            .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(user.current_user().await.unwrap().role, Role::User);
+}
+
+#[tokio::test]
+async fn admins_manage_roles() {
+    let server = spawn_server().await;
+    let admin = sign_up(&server, "admin@example.com", Role::Admin).await;
+    let user = sign_up(&server, "ada@example.com", Role::User).await;
+    let id = user.current_user().await.unwrap().id;
+
+    let response = admin.get("/api/admin/users").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let users: Vec<User> = response.json().await.unwrap();
+    let emails: Vec<_> = users.iter().map(|user| user.email.as_str()).collect();
+    assert_eq!(emails, ["admin@example.com", "ada@example.com"]);
+
+    assert_eq!(
+        admin
+            .post("/api/admin/role", json!({ "id": id, "role": "admin" }))
+            .await,
+        StatusCode::OK
+    );
+    assert_eq!(user.current_user().await.unwrap().role, Role::Admin);
+    assert_eq!(user.get("/api/admin/users").await.status(), StatusCode::OK);
+}
@@ -0,0 +1,101 @@
+//! Drives the auth server functions over HTTP, against a server on a local
+//! port with an in-memory database, the way a browser would: with a cookie jar.
+#![cfg(feature = "server")]
+
+mod common;
+
+use common::{spawn_server, Client};
+use dioxus_fullstack_templates::auth::Role;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn register_signs_in() {
+    let server = spawn_server().await;
+    let client = Client::new(&server);
+    assert_eq!(client.current_user().await, None);
+
+    assert_eq!(
+        client.register("ada@example.com", "correct horse").await,
+        StatusCode::OK
+    );
+    let user = client.current_user().await.unwrap();
+    assert_eq!(user.email, "ada@example.com");
+    assert_eq!(user.role, Role::User);
+}
+
+#[tokio::test]
+async fn logout_then_login() {
+    let server = spawn_server().await;
+    let client = Client::new(&server);
+    client.register("ada@example.com", "correct horse").await;
+
+    assert_eq!(client.logout().await, StatusCode::OK);
+    assert_eq!(client.current_user().await, None);
+
+    // Emails match case-insensitively.
+    assert_eq!(
+        client.login("ADA@example.com", "correct horse").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        client.current_user().await.unwrap().email,
+        "ada@example.com"
+    );
+}
+
+#[tokio::test]
+async fn sessions_are_per_client() {
+    let server = spawn_server().await;
+    let ada = Client::new(&server);
+    ada.register("ada@example.com", "correct horse").await;
+
+    assert_eq!(Client::new(&server).current_user().await, None);
+}
+
+#[tokio::test]
+async fn wrong_credentials_are_unauthorized() {
+    let server = spawn_server().await;
+    let client = Client::new(&server);
+    client.register("ada@example.com", "correct horse").await;
+    client.logout().await;
+
+    assert_eq!(
+        client.login("ada@example.com", "wrong horse").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        client.login("bob@example.com", "correct horse").await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(client.current_user().await, None);
+}
+
+#[tokio::test]
+async fn duplicate_email_is_a_conflict() {
+    let server = spawn_server().await;
+    let client = Client::new(&server);
+    client.register("ada@example.com", "correct horse").await;
+
+    assert_eq!(
+        Client::new(&server)
+            .register("Ada@Example.com", "another horse")
+            .await,
+        StatusCode::CONFLICT
+    );
+}
+
+#[tokio::test]
+async fn invalid_registration_is_rejected() {
+    let server = spawn_server().await;
+    let client = Client::new(&server);
+
+    assert_eq!(
+        client.register("not an email", "correct horse").await,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    assert_eq!(
+        client.register("ada@example.com", "short").await,
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    assert_eq!(client.current_user().await, None);
+}
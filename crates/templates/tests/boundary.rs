@@ -0,0 +1,61 @@
+//! Renders the post page inside the error boundary, as the router does, with
+//! its server function running against an in-memory database.
+#![cfg(feature = "server")]
+
+use dioxus::fullstack::FullstackContext;
+use dioxus::prelude::*;
+use dioxus::server::http::Request;
+use dioxus_fullstack_templates::boundary::{AppErrorBoundary, PostPage};
+use dioxus_fullstack_templates::crud::{create_post, db, PostInput};
+
+#[component]
+fn Page(id: i64) -> Element {
+    rsx! {
+        AppErrorBoundary { PostPage { id } }
+    }
+}
+
+/// The page for post `id` before its data is there, and after, on a server
+/// whose database has one post, with id 1.
+async fn render(id: i64) -> (String, String) {
+    let pool = db::connect("sqlite::memory:").await.unwrap();
+    let (mut parts, ()) = Request::new(()).into_parts();
+    parts.extensions.insert(pool);
+
+    FullstackContext::new(parts)
+        .scope(async move {
+            create_post(PostInput {
+                title: "Hello".to_string(),
+                body: "First post".to_string(),
+            })
+            .await
+            .unwrap();
+
+            let mut dom = VirtualDom::new_with_props(Page, PageProps { id });
+            dom.rebuild_in_place();
+            let loading = dioxus::ssr::render(&dom);
+            dom.wait_for_suspense().await;
+            (loading, dioxus::ssr::render(&dom))
+        })
+        .await
+}
+
+#[tokio::test]
+async fn shows_a_skeleton_then_the_post() {
+    let (loading, loaded) = render(1).await;
+
+    assert!(loading.contains(r#"class="skeleton""#), "{loading}");
+    assert_eq!(loading.matches(r#"class="skeleton-line""#).count(), 6);
+    assert!(loaded.contains("<h1>Hello</h1>"), "{loaded}");
+    assert!(!loaded.contains("skeleton"), "{loaded}");
+}
+
+#[tokio::test]
+async fn failing_server_function_shows_the_fallback() {
+    let (_, html) = render(42).await;
+
+    assert!(html.contains(r#"class="error-fallback""#), "{html}");
+    assert!(html.contains("<h1>Not found</h1>"), "{html}");
+    assert!(html.contains("Try again"), "{html}");
+    assert!(!html.contains(r#"class="post""#), "{html}");
+}
@@ -0,0 +1,177 @@
+//! WebSocket clients in the chat rooms of a local server: two speaking the
+//! template's JSON messages by hand, then the template's own client.
+#![cfg(feature = "server")]
+
+mod common;
+
+use std::time::Duration;
+
+use dioxus_fullstack_templates::chat::{ClientMessage, ServerMessage};
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+fn url(server: &common::Server, room: &str, name: &str) -> String {
+    let host = server.url.trim_start_matches("http://");
+    format!("ws://{host}/ws/chat/{room}?name={name}")
+}
+
+async fn join(server: &common::Server, room: &str, name: &str) -> Socket {
+    let (socket, _) = tokio_tungstenite::connect_async(url(server, room, name))
+        .await
+        .unwrap();
+    socket
+}
+
+async fn say(socket: &mut Socket, text: &str) {
+    let message = ClientMessage::Say {
+        text: text.to_string(),
+    };
+    let json = serde_json::to_string(&message).unwrap();
+    socket.send(Message::Text(json.into())).await.unwrap();
+}
+
+/// The next chat message, skipping control frames.
+async fn next(socket: &mut Socket) -> ServerMessage {
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("a message within 5s")
+            .expect("the socket is open")
+            .unwrap();
+        if let Message::Text(text) = frame {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+fn joined(name: &str) -> ServerMessage {
+    ServerMessage::Joined {
+        name: name.to_string(),
+    }
+}
+
+fn presence(members: &[&str]) -> ServerMessage {
+    ServerMessage::Presence {
+        members: members.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn members_chat_and_see_who_is_here() {
+    let server = common::spawn_server().await;
+
+    let mut ada = join(&server, "lobby", "ada").await;
+    assert_eq!(next(&mut ada).await, joined("ada"));
+    assert_eq!(next(&mut ada).await, presence(&["ada"]));
+
+    let mut bob = join(&server, "lobby", "bob").await;
+    for socket in [&mut ada, &mut bob] {
+        assert_eq!(next(socket).await, joined("bob"));
+        assert_eq!(next(socket).await, presence(&["ada", "bob"]));
+    }
+
+    say(&mut ada, "hello bob").await;
+    for socket in [&mut ada, &mut bob] {
+        assert_eq!(
+            next(socket).await,
+            ServerMessage::Said {
+                from: "ada".to_string(),
+                text: "hello bob".to_string(),
+            }
+        );
+    }
+
+    bob.close(None).await.unwrap();
+    assert_eq!(
+        next(&mut ada).await,
+        ServerMessage::Left {
+            name: "bob".to_string()
+        }
+    );
+    assert_eq!(next(&mut ada).await, presence(&["ada"]));
+}
+
+#[tokio::test]
+async fn rooms_are_separate() {
+    let server = common::spawn_server().await;
+    let mut ada = join(&server, "one", "ada").await;
+    let mut bob = join(&server, "two", "bob").await;
+    for _ in 0..2 {
+        next(&mut ada).await;
+        next(&mut bob).await;
+    }
+
+    say(&mut bob, "anyone?").await;
+    say(&mut ada, "just me").await;
+
+    // Ada's own message is the first she hears: Bob's went to his room.
+    assert_eq!(
+        next(&mut ada).await,
+        ServerMessage::Said {
+            from: "ada".to_string(),
+            text: "just me".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn blank_messages_are_dropped() {
+    let server = common::spawn_server().await;
+    let mut ada = join(&server, "lobby", "ada").await;
+    next(&mut ada).await;
+    next(&mut ada).await;
+
+    say(&mut ada, "   ").await;
+    say(&mut ada, "still here").await;
+    assert_eq!(
+        next(&mut ada).await,
+        ServerMessage::Said {
+            from: "ada".to_string(),
+            text: "still here".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn bad_rooms_and_names_are_refused() {
+    let server = common::spawn_server().await;
+
+    for (room, name, status) in [("lobby", "%20", 422), ("no_such_room!", "ada", 404)] {
+        match tokio_tungstenite::connect_async(url(&server, room, name)).await {
+            Err(tungstenite::Error::Http(response)) => assert_eq!(response.status(), status),
+            other => panic!("expected a refusal, got {other:?}"),
+        }
+    }
+}
+
+/// The template's own client, as desktop and mobile apps use it.
+#[tokio::test]
+async fn template_client_talks_to_the_route() {
+    use dioxus_fullstack_templates::chat::connect;
+
+    // The only test in this binary that sets it: it can be set once.
+    let server = common::spawn_server().await;
+    dioxus::fullstack::set_server_url(server.url.clone().leak());
+
+    let client = connect("lobby", "ada").await.unwrap();
+    assert_eq!(client.recv().await.unwrap(), joined("ada"));
+    assert_eq!(client.recv().await.unwrap(), presence(&["ada"]));
+
+    client
+        .send(ClientMessage::Say {
+            text: "over the native client".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        client.recv().await.unwrap(),
+        ServerMessage::Said {
+            from: "ada".to_string(),
+            text: "over the native client".to_string(),
+        }
+    );
+}
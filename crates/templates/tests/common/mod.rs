@@ -0,0 +1,116 @@
+//! A server on a local port serving what `main` serves, and a client
+//! with a cookie jar, for tests that call server functions over HTTP.
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+use dioxus_fullstack_templates::auth::User;
+use dioxus_fullstack_templates::crud::db::{self, Db};
+use dioxus_fullstack_templates::server;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+pub struct Server {
+    pub url: String,
+    /// The server's database, to set up what the API cannot.
+    pub db: Db,
+    /// Where uploads are stored, removed with the server.
+    pub uploads: TempDir,
+    /// Where prerendered pages are served from, removed with the server.
+    pub dist: TempDir,
+}
+
+/// Points Dioxus at an empty directory of built assets: without `dx` there
+/// are none, but `serve_dioxus_application` wants the directory. Pages are
+/// still rendered, without the client's scripts.
+pub fn no_assets() {
+    static PUBLIC: OnceLock<TempDir> = OnceLock::new();
+    PUBLIC.get_or_init(|| {
+        let public = tempfile::tempdir().unwrap();
+        std::env::set_var("DIOXUS_PUBLIC_PATH", public.path());
+        public
+    });
+}
+
+/// Serves the router `main` serves, with an in-memory database and
+/// temporary upload and prerendered page directories.
+pub async fn spawn_server() -> Server {
+    no_assets();
+    let pool = db::connect("sqlite::memory:").await.unwrap();
+    let uploads = tempfile::tempdir().unwrap();
+    let dist = tempfile::tempdir().unwrap();
+    let router = server::router(
+        pool.clone(),
+        uploads.path().to_path_buf(),
+        dist.path().to_path_buf(),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        dioxus::server::axum::serve(listener, router).await.unwrap();
+    });
+    Server {
+        url: format!("http://{addr}"),
+        db: pool,
+        uploads,
+        dist,
+    }
+}
+
+/// A client that keeps cookies between requests, like a browser tab.
+pub struct Client {
+    http: reqwest::Client,
+    base: String,
+}
+
+impl Client {
+    pub fn new(server: &Server) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .cookie_store(true)
+                .build()
+                .unwrap(),
+            base: server.url.clone(),
+        }
+    }
+
+    pub async fn post(&self, path: &str, body: Value) -> StatusCode {
+        self.http
+            .post(format!("{}{path}", self.base))
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    pub async fn get(&self, path: &str) -> reqwest::Response {
+        self.http
+            .get(format!("{}{path}", self.base))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn register(&self, email: &str, password: &str) -> StatusCode {
+        let credentials = json!({ "email": email, "password": password });
+        self.post("/api/auth/register", credentials).await
+    }
+
+    pub async fn login(&self, email: &str, password: &str) -> StatusCode {
+        let credentials = json!({ "email": email, "password": password });
+        self.post("/api/auth/login", credentials).await
+    }
+
+    pub async fn logout(&self) -> StatusCode {
+        self.post("/api/auth/logout", json!({})).await
+    }
+
+    pub async fn current_user(&self) -> Option<User> {
+        let response = self.get("/api/auth/me").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.unwrap()
+    }
+}
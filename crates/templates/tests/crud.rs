@@ -0,0 +1,121 @@
+//! Runs the CRUD server functions against an in-memory SQLite database.
+#![cfg(feature = "server")]
+
+use std::future::Future;
+
+use dioxus::fullstack::FullstackContext;
+use dioxus::server::http::Request;
+use dioxus_fullstack_templates::crud::{
+    create_post, db, delete_post, get_post, list_posts, update_post, Post, PostInput,
+};
+use dioxus_fullstack_templates::error::{AppError, FORM_FIELD};
+
+/// Runs `f` as if it were handling a request on a server whose router has
+/// `.layer(Extension(pool))` for a fresh, migrated database.
+async fn with_db<F: Future>(f: impl FnOnce() -> F) -> F::Output {
+    let pool = db::connect("sqlite::memory:").await.unwrap();
+    let (mut parts, ()) = Request::new(()).into_parts();
+    parts.extensions.insert(pool);
+
+    FullstackContext::new(parts).scope(f()).await
+}
+
+fn input(title: &str, body: &str) -> PostInput {
+    PostInput {
+        title: title.to_string(),
+        body: body.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn create_then_read() {
+    with_db(|| async {
+        let created = create_post(input("Hello", "First post")).await.unwrap();
+        let expected = Post {
+            id: created.id,
+            title: "Hello".to_string(),
+            body: "First post".to_string(),
+        };
+
+        assert_eq!(created, expected);
+        assert_eq!(get_post(created.id).await.unwrap(), expected);
+        assert_eq!(list_posts().await.unwrap(), [expected]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn list_is_ordered_by_id() {
+    with_db(|| async {
+        for title in ["b", "a", "c"] {
+            create_post(input(title, "")).await.unwrap();
+        }
+
+        let titles: Vec<_> = list_posts()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|post| post.title)
+            .collect();
+        assert_eq!(titles, ["b", "a", "c"]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn update_and_delete() {
+    with_db(|| async {
+        let post = create_post(input("Draft", "")).await.unwrap();
+
+        let updated = update_post(post.id, input("Published", "Done"))
+            .await
+            .unwrap();
+        assert_eq!(updated.title, "Published");
+        assert_eq!(get_post(post.id).await.unwrap(), updated);
+
+        delete_post(post.id).await.unwrap();
+        assert_eq!(list_posts().await.unwrap(), []);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn missing_post_is_not_found() {
+    with_db(|| async {
+        assert_eq!(get_post(42).await.unwrap_err(), AppError::NotFound);
+        assert_eq!(
+            update_post(42, input("x", "")).await.unwrap_err(),
+            AppError::NotFound
+        );
+        assert_eq!(delete_post(42).await.unwrap_err(), AppError::NotFound);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn duplicate_title_is_a_conflict() {
+    with_db(|| async {
+        create_post(input("Hello", "")).await.unwrap();
+
+        let err = create_post(input("Hello", "again")).await.unwrap_err();
+        assert_eq!(
+            err,
+            AppError::Conflict {
+                message: "already exists".to_string()
+            }
+        );
+        assert_eq!(list_posts().await.unwrap().len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn blank_title_is_rejected() {
+    with_db(|| async {
+        assert_eq!(
+            create_post(input("  ", "")).await.unwrap_err(),
+            AppError::validation(FORM_FIELD, "invalid value")
+        );
+    })
+    .await;
+}
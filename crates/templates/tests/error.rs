@@ -0,0 +1,133 @@
+//! Round-trips `AppError` through the server function error encoding, and
+//! checks what the server sends for it.
+
+#[cfg(feature = "server")]
+mod common;
+
+use dioxus::prelude::*;
+use dioxus::CapturedError;
+use dioxus_fullstack_templates::error::{AppError, FieldError};
+
+fn every_variant() -> Vec<AppError> {
+    vec![
+        AppError::validation("title", "Title cannot be empty"),
+        AppError::NotFound,
+        AppError::Unauthorized,
+        AppError::Forbidden,
+        AppError::Conflict {
+            message: "already exists".to_string(),
+        },
+        AppError::TooLarge,
+        AppError::Internal,
+    ]
+}
+
+#[test]
+fn variants_map_to_statuses() {
+    let statuses: Vec<u16> = every_variant()
+        .iter()
+        .map(|err| err.status().as_u16())
+        .collect();
+    assert_eq!(statuses, [422, 404, 401, 403, 409, 413, 500]);
+}
+
+#[test]
+fn round_trips_through_server_fn_error() {
+    for err in every_variant() {
+        let encoded = ServerFnError::from(err.clone());
+        match &encoded {
+            ServerFnError::ServerError { code, .. } => assert_eq!(*code, err.status().as_u16()),
+            other => panic!("expected a server error, got {other:?}"),
+        }
+        assert_eq!(AppError::from(encoded), err);
+    }
+}
+
+#[test]
+fn plain_server_errors_map_by_status() {
+    let server_error = |code| ServerFnError::ServerError {
+        message: "message".to_string(),
+        code,
+        details: None,
+    };
+
+    assert_eq!(AppError::from(server_error(401)), AppError::Unauthorized);
+    assert_eq!(AppError::from(server_error(403)), AppError::Forbidden);
+    assert_eq!(AppError::from(server_error(404)), AppError::NotFound);
+    assert_eq!(
+        AppError::from(server_error(409)),
+        AppError::Conflict {
+            message: "message".to_string()
+        }
+    );
+    assert_eq!(AppError::from(server_error(503)), AppError::Internal);
+    assert_eq!(
+        AppError::from(ServerFnError::Request(
+            dioxus::fullstack::RequestError::Request("connection refused".to_string())
+        )),
+        AppError::Internal
+    );
+}
+
+#[test]
+fn survives_captured_error() {
+    for err in every_variant() {
+        assert_eq!(AppError::from(CapturedError::new(err.clone())), err);
+    }
+}
+
+#[test]
+fn field_error_renders_only_its_field() {
+    fn app() -> Element {
+        let error = Some(AppError::validation("title", "Title cannot be empty"));
+        rsx! {
+            FieldError { error: error.clone(), field: "title" }
+            FieldError { error: error.clone(), field: "body" }
+            FieldError { error: AppError::NotFound, field: "title" }
+            FieldError { error: None, field: "title" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(
+        dioxus::ssr::render(&dom),
+        r#"<p class="field-error">Title cannot be empty</p>"#
+    );
+}
+
+#[cfg(feature = "server")]
+#[tokio::test]
+async fn server_responds_with_status_and_variant() {
+    use dioxus::server::ServerFunction;
+    use dioxus_fullstack_templates::server_function::server_function;
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    // Called directly, the server function returns the variant as is.
+    assert_eq!(
+        server_function("x".repeat(101)).await.unwrap_err().status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+
+    // Over HTTP, the status and the variant are in the response.
+    let server = common::spawn_server().await;
+    let path = ServerFunction::collect()
+        .into_iter()
+        .map(ServerFunction::path)
+        .find(|path| path.contains("server_function"))
+        .unwrap();
+    let response = reqwest::Client::new()
+        .post(format!("{}{path}", server.url))
+        .json(&json!({ "param": "x".repeat(101) }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        serde_json::from_value::<AppError>(body["data"].clone()).unwrap(),
+        AppError::validation("query", "Keep it under 100 characters")
+    );
+}
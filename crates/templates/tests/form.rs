@@ -0,0 +1,236 @@
+//! Drives `use_form` in a `VirtualDom`: fields are changed from outside the
+//! way input events would, then the state and the rendered markup checked.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_fullstack_templates::error::{AppError, FORM_FIELD};
+use dioxus_fullstack_templates::form::{use_form, Form, FormError, SignUp, TextInput};
+use validator::Validate;
+
+type Slot = Rc<Cell<Option<Form<SignUp>>>>;
+
+/// Renders an email input for a `SignUp` form and hands the form out.
+fn app() -> Element {
+    let form = use_form::<SignUp>();
+    use_hook(|| consume_context::<Slot>().set(Some(form)));
+
+    rsx! {
+        TextInput { field: form.field("email"), label: "Email" }
+        FormError { field: form.form_error() }
+    }
+}
+
+struct Harness {
+    dom: VirtualDom,
+    form: Form<SignUp>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let slot = Slot::default();
+        let mut dom = VirtualDom::new(app).with_root_context(slot.clone());
+        dom.rebuild_in_place();
+        let form = slot.get().unwrap();
+        Self { dom, form }
+    }
+
+    /// Runs `f` against the form, then renders what it changed.
+    fn update<O>(&mut self, f: impl FnOnce(Form<SignUp>) -> O) -> O {
+        let out = self.dom.in_runtime(|| f(self.form));
+        self.dom
+            .render_immediate(&mut dioxus::dioxus_core::NoOpMutations);
+        out
+    }
+
+    fn html(&self) -> String {
+        dioxus::ssr::render(&self.dom)
+    }
+
+    fn email_error(&mut self) -> Option<String> {
+        self.update(|form| form.field("email").error())
+    }
+}
+
+fn fill_valid(form: Form<SignUp>) {
+    form.field("email").set_text("ada@example.com".to_string());
+    form.field("password").set_text("correct horse".to_string());
+    form.field("plan").set_text("pro".to_string());
+    form.field("accept_terms").set_checked(true);
+}
+
+#[test]
+fn errors_show_once_touched() {
+    let mut harness = Harness::new();
+    assert_eq!(harness.email_error(), None);
+    assert!(!harness.html().contains("field-error"));
+
+    harness.update(|form| {
+        form.field("email").set_text("ada".to_string());
+        form.field("email").touch();
+    });
+    assert_eq!(
+        harness.email_error().as_deref(),
+        Some("Enter a valid email address")
+    );
+    assert!(harness
+        .html()
+        .contains(r#"<p class="field-error">Enter a valid email address</p>"#));
+
+    harness.update(|form| form.field("email").set_text("ada@example.com".to_string()));
+    assert_eq!(harness.email_error(), None);
+}
+
+#[test]
+fn submit_shows_every_error() {
+    let mut harness = Harness::new();
+
+    assert_eq!(harness.update(|form| form.submit()), None);
+    let errors = harness.update(|form| {
+        ["email", "password", "plan", "bio", "accept_terms"].map(|name| form.field(name).error())
+    });
+    assert_eq!(
+        errors,
+        [
+            Some("Enter a valid email address".to_string()),
+            Some("Use at least 8 characters".to_string()),
+            Some("Choose a plan".to_string()),
+            None,
+            Some("Accept the terms to continue".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn submit_returns_valid_data() {
+    let mut harness = Harness::new();
+    harness.update(fill_valid);
+
+    assert_eq!(
+        harness.update(|form| form.submit()),
+        Some(SignUp {
+            email: "ada@example.com".to_string(),
+            password: "correct horse".to_string(),
+            plan: "pro".to_string(),
+            bio: String::new(),
+            accept_terms: true,
+        })
+    );
+}
+
+#[test]
+fn tracks_touched_and_dirty() {
+    let mut harness = Harness::new();
+    let state = |form: Form<SignUp>| {
+        let email = form.field("email");
+        (email.is_touched(), email.is_dirty(), form.is_dirty())
+    };
+    assert_eq!(harness.update(state), (false, false, false));
+
+    harness.update(|form| form.field("email").set_text("ada".to_string()));
+    assert_eq!(harness.update(state), (false, true, true));
+
+    harness.update(|form| {
+        form.field("email").set_text(String::new());
+        form.field("email").touch();
+    });
+    assert_eq!(harness.update(state), (true, false, false));
+
+    harness.update(|form| form.reset());
+    assert_eq!(harness.update(state), (false, false, false));
+}
+
+#[test]
+fn server_errors_show_until_the_field_changes() {
+    let mut harness = Harness::new();
+    harness.update(fill_valid);
+
+    harness
+        .update(|form| form.set_server_error(&AppError::validation("email", "Already registered")));
+    assert_eq!(harness.email_error().as_deref(), Some("Already registered"));
+    assert!(harness.html().contains("Already registered"));
+
+    harness.update(|form| form.field("email").set_text("bob@example.com".to_string()));
+    assert_eq!(harness.email_error(), None);
+}
+
+#[test]
+fn malformed_values_are_a_form_error() {
+    let mut harness = Harness::new();
+    harness.update(fill_valid);
+    // Text where `SignUp` has a `bool`: it does not parse at all.
+    harness.update(|form| form.field("accept_terms").set_text("yes".to_string()));
+
+    assert_eq!(harness.update(|form| form.submit()), None);
+    let error = harness.update(|form| form.form_error().error()).unwrap();
+    assert!(error.contains("expected a boolean"), "{error}");
+    assert!(harness
+        .html()
+        .contains(r#"<p class="form-error" role="alert">"#));
+
+    harness.update(|form| form.field("accept_terms").set_checked(true));
+    assert_eq!(harness.update(|form| form.form_error().error()), None);
+}
+
+#[test]
+fn server_errors_about_the_form_show_until_any_field_changes() {
+    let mut harness = Harness::new();
+    harness.update(fill_valid);
+
+    harness.update(|form| form.set_server_error(&AppError::validation(FORM_FIELD, "Try again")));
+    assert!(harness
+        .html()
+        .contains(r#"<p class="form-error" role="alert">Try again</p>"#));
+
+    harness.update(|form| form.field("bio").set_text("Hi".to_string()));
+    assert!(!harness.html().contains("form-error"));
+}
+
+#[test]
+fn schema_errors_are_a_form_error() {
+    let mut harness = Harness::new();
+    harness.update(fill_valid);
+    harness.update(|form| {
+        form.field("password")
+            .set_text("ada@example.com".to_string())
+    });
+
+    let input = harness.update(|form| form.value()).unwrap();
+    assert_eq!(harness.update(|form| form.submit()), None);
+    assert!(harness.html().contains(
+        r#"<p class="form-error" role="alert">Use a password other than your email</p>"#
+    ));
+    // The server reports it the same way.
+    assert_eq!(
+        AppError::from(input.validate().unwrap_err()),
+        AppError::validation(FORM_FIELD, "Use a password other than your email")
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "`emial` is not a field of")]
+fn misspelled_fields_panic() {
+    let mut harness = Harness::new();
+    harness.update(|form| form.field("emial"));
+}
+
+#[cfg(feature = "server")]
+#[tokio::test]
+async fn server_function_validates_again() {
+    use dioxus_fullstack_templates::form::sign_up;
+
+    // What a client skipping the form's checks could send.
+    let input = SignUp {
+        email: "ada@example.com".to_string(),
+        password: "short".to_string(),
+        plan: "pro".to_string(),
+        bio: String::new(),
+        accept_terms: true,
+    };
+    assert_eq!(
+        sign_up(input).await.unwrap_err(),
+        AppError::validation("password", "Use at least 8 characters")
+    );
+}
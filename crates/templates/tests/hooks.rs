@@ -0,0 +1,282 @@
+//! Runs the custom hooks in a headless `VirtualDom`. Each app hands its
+//! signals and handles out through a root context, the test changes them
+//! from outside and lets the dom's tasks run until the expected state comes
+//! about. Tests wait for what should happen rather than for a set time, so a
+//! slow machine makes them slower, not wrong; only checks that something
+//! does *not* happen wait a fixed while.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::dioxus_core::NoOpMutations;
+use dioxus::prelude::*;
+use dioxus_fullstack_templates::error::AppError;
+use dioxus_fullstack_templates::hooks::{
+    use_api, use_debounce, use_interval, use_previous, use_timeout, use_toggle, Api, Timer, Toggle,
+};
+
+/// What the app under test hands out on its first render.
+type Slot<T> = Rc<RefCell<Option<T>>>;
+
+fn hand_out<T: Clone + 'static>(value: T) {
+    use_hook(|| consume_context::<Slot<T>>().replace(Some(value)));
+}
+
+fn mount<T: Clone + 'static>(app: fn() -> Element) -> (VirtualDom, T) {
+    let slot = Slot::<T>::default();
+    let mut dom = VirtualDom::new(app).with_root_context(slot.clone());
+    dom.rebuild_in_place();
+    let handed_out = slot.borrow().clone().expect("the app hands out its state");
+    (dom, handed_out)
+}
+
+/// Runs the effects and woken tasks that are ready, and renders, without
+/// waiting for anything.
+fn settle(dom: &mut VirtualDom) {
+    dom.render_immediate(&mut NoOpMutations);
+}
+
+/// Lets the dom run its tasks and effects, and render, for `duration`.
+async fn run_for(dom: &mut VirtualDom, duration: Duration) {
+    let work = async {
+        loop {
+            dom.wait_for_work().await;
+            dom.render_immediate(&mut NoOpMutations);
+        }
+    };
+    let _ = tokio::time::timeout(duration, work).await;
+}
+
+/// Lets the dom run until `done` holds, read in its runtime. Signals only
+/// the test reads re-render nothing, so `done` is checked every few
+/// milliseconds rather than after each render. Fails after a limit far above
+/// any delay here.
+async fn run_until(dom: &mut VirtualDom, done: impl Fn() -> bool) {
+    let work = async {
+        settle(dom);
+        while !dom.in_runtime(&done) {
+            run_for(dom, Duration::from_millis(5)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), work)
+        .await
+        .expect("the dom gets there");
+}
+
+/// How long checks that nothing happens wait.
+const QUIET: Duration = Duration::from_millis(200);
+
+#[tokio::test]
+async fn debounce_lets_only_the_last_value_through() {
+    type State = (Signal<String>, Signal<Vec<String>>);
+    fn app() -> Element {
+        let input = use_signal(String::new);
+        let debounced = use_debounce(input.into(), Duration::from_millis(100));
+        let mut seen = use_signal(Vec::new);
+        use_effect(move || seen.write().push(debounced()));
+        hand_out::<State>((input, seen));
+        rsx! {}
+    }
+
+    let (mut dom, (mut input, seen)) = mount::<State>(app);
+    // Each change runs the effect, well within the delay of the one before.
+    for text in ["a", "ab", "abc"] {
+        dom.in_runtime(|| input.set(text.to_string()));
+        settle(&mut dom);
+    }
+    assert_eq!(dom.in_runtime(|| seen.cloned()), [""]);
+
+    run_until(&mut dom, || seen.len() > 1).await;
+    assert_eq!(dom.in_runtime(|| seen.cloned()), ["", "abc"]);
+}
+
+#[tokio::test]
+async fn debounce_drops_a_change_that_is_undone() {
+    type State = (Signal<u32>, Signal<Vec<u32>>);
+    fn app() -> Element {
+        let input = use_signal(|| 0);
+        let debounced = use_debounce(input.into(), Duration::from_millis(50));
+        let mut seen = use_signal(Vec::new);
+        use_effect(move || seen.write().push(debounced()));
+        hand_out::<State>((input, seen));
+        rsx! {}
+    }
+
+    let (mut dom, (mut input, seen)) = mount::<State>(app);
+    dom.in_runtime(|| input.set(1));
+    settle(&mut dom);
+    dom.in_runtime(|| input.set(0));
+    run_for(&mut dom, QUIET).await;
+    assert_eq!(dom.in_runtime(|| seen.cloned()), [0]);
+}
+
+#[tokio::test]
+async fn api_loads_refetches_and_types_errors() {
+    type State = (Api<u32>, Signal<bool>);
+    fn app() -> Element {
+        let mut calls = use_hook(|| CopyValue::new(0));
+        let failing = use_signal(|| false);
+        let api = use_api(move || {
+            calls += 1;
+            let (calls, failing) = (calls(), failing());
+            async move {
+                match failing {
+                    false => Ok(calls),
+                    true => Err(ServerFnError::ServerError {
+                        message: "gone".to_string(),
+                        code: 404,
+                        details: None,
+                    }),
+                }
+            }
+        });
+        hand_out::<State>((api, failing));
+        rsx! {}
+    }
+
+    let (mut dom, (api, mut failing)) = mount::<State>(app);
+    run_until(&mut dom, || !api.loading()).await;
+    assert_eq!(dom.in_runtime(|| api.data()), Some(1));
+
+    dom.in_runtime(|| api.refetch());
+    run_until(&mut dom, || api.data() == Some(2)).await;
+
+    // Reading `failing` made it a dependency: changing it fetches again.
+    dom.in_runtime(|| failing.set(true));
+    run_until(&mut dom, || api.error().is_some()).await;
+    assert_eq!(
+        dom.in_runtime(|| (api.data(), api.error())),
+        (None, Some(AppError::NotFound))
+    );
+}
+
+#[tokio::test]
+async fn timeout_fires_once_and_restarts() {
+    type State = (Timer, Signal<u32>);
+    fn app() -> Element {
+        let mut fired = use_signal(|| 0);
+        let timer = use_timeout(Duration::from_millis(30), move || fired += 1);
+        hand_out::<State>((timer, fired));
+        rsx! {}
+    }
+
+    let (mut dom, (timer, fired)) = mount::<State>(app);
+    assert!(dom.in_runtime(|| timer.is_running()));
+    run_until(&mut dom, || fired.cloned() > 0).await;
+    assert!(!dom.in_runtime(|| timer.is_running()));
+    run_for(&mut dom, QUIET).await;
+    assert_eq!(dom.in_runtime(|| fired.cloned()), 1);
+
+    dom.in_runtime(|| timer.restart());
+    run_until(&mut dom, || fired.cloned() == 2).await;
+
+    dom.in_runtime(|| timer.restart());
+    dom.in_runtime(|| timer.cancel());
+    run_for(&mut dom, QUIET).await;
+    assert_eq!(dom.in_runtime(|| (fired(), timer.is_running())), (2, false));
+}
+
+#[tokio::test]
+async fn interval_ticks_until_cancelled() {
+    type State = (Timer, Signal<u32>);
+    fn app() -> Element {
+        let mut ticks = use_signal(|| 0);
+        let timer = use_interval(Duration::from_millis(20), move || ticks += 1);
+        hand_out::<State>((timer, ticks));
+        rsx! {}
+    }
+
+    let (mut dom, (timer, ticks)) = mount::<State>(app);
+    run_until(&mut dom, || ticks.cloned() >= 3).await;
+
+    dom.in_runtime(|| timer.cancel());
+    let ticked = dom.in_runtime(|| ticks.cloned());
+    run_for(&mut dom, QUIET).await;
+    assert_eq!(dom.in_runtime(|| ticks.cloned()), ticked);
+
+    dom.in_runtime(|| timer.restart());
+    run_until(&mut dom, || ticks.cloned() > ticked).await;
+}
+
+#[tokio::test]
+async fn timers_stop_when_the_component_unmounts() {
+    type State = (Signal<bool>, Signal<u32>);
+    #[component]
+    fn Ticker(ticks: Signal<u32>) -> Element {
+        use_interval(Duration::from_millis(10), move || ticks += 1);
+        rsx! {}
+    }
+    fn app() -> Element {
+        let shown = use_signal(|| true);
+        let ticks = use_signal(|| 0);
+        hand_out::<State>((shown, ticks));
+        rsx! {
+            if shown() {
+                Ticker { ticks }
+            }
+        }
+    }
+
+    let (mut dom, (mut shown, ticks)) = mount::<State>(app);
+    run_until(&mut dom, || ticks.cloned() > 0).await;
+    dom.in_runtime(|| shown.set(false));
+    settle(&mut dom);
+    let ticked = dom.in_runtime(|| ticks.cloned());
+
+    run_for(&mut dom, QUIET).await;
+    assert_eq!(dom.in_runtime(|| ticks.cloned()), ticked);
+}
+
+#[test]
+fn previous_is_the_value_before_the_last_change() {
+    type State = (Signal<u32>, ReadSignal<Option<u32>>);
+    fn app() -> Element {
+        let value = use_signal(|| 1);
+        let previous = use_previous(value.into());
+        hand_out::<State>((value, previous));
+        rsx! {}
+    }
+
+    let (dom, (mut value, previous)) = mount::<State>(app);
+    let mut set = |to| {
+        dom.in_runtime(|| {
+            value.set(to);
+            previous()
+        })
+    };
+    assert_eq!(dom.in_runtime(|| previous.cloned()), None);
+    assert_eq!(set(2), Some(1));
+    assert_eq!(set(2), Some(1));
+    assert_eq!(set(3), Some(2));
+}
+
+#[test]
+fn toggle_flips_and_renders() {
+    fn app() -> Element {
+        let menu = use_toggle(false);
+        hand_out::<Toggle>(menu);
+        rsx! {
+            if menu.is_on() {
+                "open"
+            } else {
+                "closed"
+            }
+        }
+    }
+
+    fn render(dom: &mut VirtualDom) -> String {
+        dom.render_immediate(&mut NoOpMutations);
+        dioxus::ssr::render(dom)
+    }
+
+    let (mut dom, menu) = mount::<Toggle>(app);
+    assert_eq!(render(&mut dom), "closed");
+
+    dom.in_runtime(|| menu.toggle());
+    assert_eq!(render(&mut dom), "open");
+    dom.in_runtime(|| menu.toggle());
+    assert_eq!(render(&mut dom), "closed");
+    dom.in_runtime(|| menu.set(true));
+    assert_eq!(render(&mut dom), "open");
+}
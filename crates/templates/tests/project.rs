@@ -0,0 +1,189 @@
+//! Builds an app assembled from the templates, with `Cargo.toml`,
+//! `Dioxus.toml` and `main.rs` as its manifests and entrypoint, once per
+//! feature set `dx` builds it with.
+//!
+//! The builds are slow and need network access for a fresh lockfile, so they
+//! are ignored by default:
+//!
+//! ```text
+//! cargo test -p dioxus-fullstack-templates --test project -- --ignored
+//! ```
+//!
+//! `desktop` and `mobile` also need the WebKitGTK development packages on
+//! Linux.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn templates() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../skills/dioxus-fullstack/assets/templates")
+}
+
+fn read_template(name: &str) -> String {
+    fs::read_to_string(templates().join(name)).unwrap()
+}
+
+/// The templates that become modules of the app, next to `main.rs`.
+const MODULES: &[&str] = &[
+    "admin",
+    "auth",
+    "boundary",
+    "chat",
+    "component",
+    "crud",
+    "error",
+    "form",
+    "hooks",
+    "route",
+    "server",
+    "server_function",
+    "ssg",
+    "store",
+    "stream",
+    "streaming",
+    "upload",
+];
+
+/// Lays out a crate the way `SKILL.md` describes, from the templates.
+fn project() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir(&src).unwrap();
+
+    fs::write(dir.path().join("Cargo.toml"), read_template("Cargo.toml")).unwrap();
+    fs::write(dir.path().join("Dioxus.toml"), read_template("Dioxus.toml")).unwrap();
+    for file in MODULES.iter().map(|module| format!("{module}.rs")) {
+        fs::write(src.join(&file), read_template(&file)).unwrap();
+    }
+    fs::write(src.join("main.rs"), read_template("main.rs")).unwrap();
+
+    let migrations = dir.path().join("migrations");
+    fs::create_dir(&migrations).unwrap();
+    for entry in fs::read_dir(templates().join("migrations")).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, migrations.join(path.file_name().unwrap())).unwrap();
+    }
+
+    // Start from this workspace's versions, so the build reuses its
+    // dependencies instead of resolving newer ones.
+    let lockfile = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock");
+    if lockfile.exists() {
+        fs::copy(lockfile, dir.path().join("Cargo.lock")).unwrap();
+    }
+    dir
+}
+
+fn cargo(project: &Path, args: &[&str], features: &str) -> String {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let target_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/template-project");
+    let output = Command::new(cargo)
+        .args(args)
+        .args(["--no-default-features", "--features", features])
+        .current_dir(project)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "cargo {} --features {features} failed:\n{stderr}",
+        args.join(" ")
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Builds the app with `features`, and checks whether server-only crates
+/// are part of the build.
+fn builds(features: &str, server_only_deps: bool) {
+    let project = project();
+
+    let tree = cargo(
+        project.path(),
+        &["tree", "--edges", "normal", "--prefix", "none"],
+        features,
+    );
+    // Client platforms pull in tokio themselves, so only sqlx tells.
+    let sqlx = tree.lines().any(|line| line.starts_with("sqlx "));
+    assert_eq!(sqlx, server_only_deps, "sqlx with --features {features}");
+
+    cargo(project.path(), &["build"], features);
+}
+
+#[test]
+#[ignore = "builds a separate project"]
+fn web() {
+    builds("web", false);
+}
+
+#[test]
+#[ignore = "builds a separate project; needs WebKitGTK"]
+fn desktop() {
+    builds("desktop", false);
+}
+
+#[test]
+#[ignore = "builds a separate project; needs WebKitGTK"]
+fn mobile() {
+    builds("mobile", false);
+}
+
+#[test]
+#[ignore = "builds a separate project"]
+fn server() {
+    builds("server", true);
+}
+
+#[test]
+fn manifests_declare_every_platform() {
+    let cargo: toml::Table = read_template("Cargo.toml").parse().unwrap();
+    let features = cargo["features"].as_table().unwrap();
+    for feature in ["web", "desktop", "mobile", "server"] {
+        assert!(
+            features.contains_key(feature),
+            "missing feature `{feature}`"
+        );
+    }
+
+    // Server-only dependencies must be optional and only enabled by `server`.
+    let dependencies = cargo["dependencies"].as_table().unwrap();
+    for krate in [
+        "argon2",
+        "sha2",
+        "sqlx",
+        "tempfile",
+        "tokio",
+        "tower",
+        "tower-sessions",
+    ] {
+        assert_eq!(
+            dependencies[krate]["optional"].as_bool(),
+            Some(true),
+            "{krate}"
+        );
+        for (feature, enables) in features {
+            let enables = enables.as_array().unwrap();
+            let enabled = enables
+                .iter()
+                .any(|f| f.as_str() == Some(&format!("dep:{krate}")));
+            assert_eq!(enabled, feature == "server", "`{feature}` and `{krate}`");
+        }
+    }
+
+    let dioxus: toml::Table = read_template("Dioxus.toml").parse().unwrap();
+    assert!(dioxus.contains_key("application"));
+    assert!(dioxus["bundle"].get("identifier").is_some());
+}
+
+#[test]
+fn main_declares_every_module() {
+    let main = read_template("main.rs");
+    let declared: Vec<&str> = main
+        .lines()
+        .filter_map(|line| line.strip_prefix("mod ")?.strip_suffix(';'))
+        .collect();
+    assert_eq!(declared, MODULES);
+}
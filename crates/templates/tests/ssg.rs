@@ -0,0 +1,209 @@
+//! Prerenders the static pages into a temporary `dist` and checks the file
+//! tree, then serves pages through the page middleware with fresh and
+//! outdated files.
+#![cfg(feature = "server")]
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use dioxus::server::ServeConfig;
+use dioxus_fullstack_templates::crud::db;
+use dioxus_fullstack_templates::server;
+use dioxus_fullstack_templates::ssg::{self, page_file, TTL};
+use reqwest::StatusCode;
+
+/// Every file under `dir`, relative to it, sorted.
+fn tree(dir: &Path) -> Vec<String> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(&path, files);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(dir, &mut files);
+    let mut files: Vec<String> = files
+        .iter()
+        .map(|file| file.strip_prefix(dir).unwrap().display().to_string())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn routes_are_the_static_pages_and_the_blog_posts() {
+    let paths: Vec<String> = ssg::routes(&[1, 2])
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(paths, ["/", "/about", "/blog/1", "/blog/2"]);
+}
+
+#[test]
+fn page_files_stay_in_dist() {
+    let dist = Path::new("dist");
+    assert_eq!(page_file(dist, "/"), Some(dist.join("index.html")));
+    assert_eq!(
+        page_file(dist, "/blog/1?ref=feed"),
+        Some(dist.join("blog/1/index.html"))
+    );
+    assert_eq!(page_file(dist, "/blog/../../etc"), None);
+}
+
+#[tokio::test]
+async fn prerenders_each_page_to_its_index_html() {
+    let pool = db::connect("sqlite::memory:").await.unwrap();
+    let uploads = tempfile::tempdir().unwrap();
+    let dist = tempfile::tempdir().unwrap();
+    common::no_assets();
+    let app = server::app(ServeConfig::new(), pool, uploads.path().to_path_buf());
+
+    let files = ssg::server::prerender(app, dist.path(), &ssg::routes(&[1, 2]))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        tree(dist.path()),
+        [
+            "about/index.html",
+            "blog/1/index.html",
+            "blog/2/index.html",
+            "index.html"
+        ]
+    );
+    assert_eq!(files.len(), 4);
+
+    let post = fs::read_to_string(dist.path().join("blog/2/index.html")).unwrap();
+    assert!(post.starts_with("<!DOCTYPE html>"), "{post}");
+    assert!(post.contains("This is blog post number"), "{post}");
+    // Whole pages, with the data the client hydrates from.
+    assert!(post.contains("window.initial_dioxus_hydration_data="));
+    assert!(!post.contains("window.dx_hydrate("));
+}
+
+/// Puts a page in the server's `dist`, last modified `age` ago.
+fn prerendered(server: &common::Server, path: &str, html: &str, age: std::time::Duration) {
+    let file = page_file(server.dist.path(), path).unwrap();
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(&file, html).unwrap();
+    let modified = SystemTime::now() - age;
+    fs::File::options()
+        .write(true)
+        .open(&file)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+/// What the page's file holds once it is no longer `old`, after the render
+/// in the background.
+async fn rewritten(server: &common::Server, path: &str, old: &str) -> String {
+    let file = page_file(server.dist.path(), path).unwrap();
+    for _ in 0..500 {
+        let html = fs::read_to_string(&file).unwrap();
+        if html != old {
+            return html;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("{path} was not rendered again");
+}
+
+async fn get(server: &common::Server, path: &str) -> String {
+    let response = reqwest::get(format!("{}{path}", server.url)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response.text().await.unwrap()
+}
+
+#[tokio::test]
+async fn serves_fresh_pages_from_their_files() {
+    let server = common::spawn_server().await;
+    prerendered(&server, "/about", "<p>prerendered</p>", TTL / 2);
+
+    assert_eq!(get(&server, "/about").await, "<p>prerendered</p>");
+}
+
+#[tokio::test]
+async fn renders_pages_older_than_the_ttl_again() {
+    let server = common::spawn_server().await;
+    prerendered(&server, "/about", "<p>prerendered</p>", TTL * 2);
+
+    // The visitor gets the old page at once, rather than wait for the render.
+    assert_eq!(get(&server, "/about").await, "<p>prerendered</p>");
+
+    let html = rewritten(&server, "/about", "<p>prerendered</p>").await;
+    assert!(html.contains("<h1>About</h1>"), "{html}");
+    let file = server.dist.path().join("about/index.html");
+    let age = fs::metadata(&file).unwrap().modified().unwrap().elapsed();
+    assert!(age.unwrap() < TTL);
+    // The new file is served from now on.
+    assert_eq!(get(&server, "/about").await, html);
+}
+
+#[tokio::test]
+async fn renders_again_as_prerender_does() {
+    let server = common::spawn_server().await;
+    prerendered(&server, "/blog/1", "<p>prerendered</p>", TTL * 2);
+
+    get(&server, "/blog/1").await;
+    let html = rewritten(&server, "/blog/1", "<p>prerendered</p>").await;
+
+    let dist = tempfile::tempdir().unwrap();
+    let app = server::app(
+        ServeConfig::new(),
+        server.db.clone(),
+        server.uploads.path().to_path_buf(),
+    );
+    ssg::server::prerender(app, dist.path(), &ssg::routes(&[1]))
+        .await
+        .unwrap();
+    let expected = fs::read_to_string(dist.path().join("blog/1/index.html")).unwrap();
+    assert_eq!(html, expected);
+}
+
+#[tokio::test]
+async fn answers_requests_for_a_stale_page_at_the_same_time() {
+    let server = common::spawn_server().await;
+    prerendered(&server, "/about", "<p>prerendered</p>", TTL * 2);
+
+    let requests = (0..8).map(|_| get(&server, "/about"));
+    let pages = futures_util::future::join_all(requests).await;
+
+    // One of them starts the render; they all get the old page meanwhile,
+    // or the new one once it is there.
+    let html = rewritten(&server, "/about", "<p>prerendered</p>").await;
+    assert!(html.contains("<h1>About</h1>"), "{html}");
+    for page in pages {
+        assert!(page == html || page == "<p>prerendered</p>", "{page}");
+    }
+}
+
+#[tokio::test]
+async fn passes_server_functions_through() {
+    let server = common::spawn_server().await;
+    prerendered(&server, "/api/posts", "<p>prerendered</p>", TTL / 2);
+
+    let response = reqwest::get(format!("{}/api/posts", server.url))
+        .await
+        .unwrap();
+
+    assert_ne!(response.text().await.unwrap(), "<p>prerendered</p>");
+}
+
+#[tokio::test]
+async fn leaves_pages_that_were_not_prerendered_alone() {
+    let server = common::spawn_server().await;
+
+    let html = get(&server, "/blog/7").await;
+
+    assert!(html.contains("This is blog post number"), "{html}");
+    assert!(tree(server.dist.path()).is_empty());
+}
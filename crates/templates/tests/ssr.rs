@@ -0,0 +1,115 @@
+//! Renders each template to HTML and compares it against the snapshots in
+//! `tests/snapshots`. Review markup changes with `cargo insta review`.
+
+use std::rc::Rc;
+
+use dioxus::history::{provide_history_context, MemoryHistory};
+use dioxus::prelude::*;
+use dioxus_fullstack_templates::component::{ActionCard, Component};
+use dioxus_fullstack_templates::route::App;
+use dioxus_fullstack_templates::server_function::ServerComponent;
+
+fn render(dom: &mut VirtualDom) -> String {
+    dom.rebuild_in_place();
+    dioxus::ssr::render(dom)
+}
+
+fn render_route(path: &str) -> String {
+    #[component]
+    fn RouteAt(path: String) -> Element {
+        use_hook(|| provide_history_context(Rc::new(MemoryHistory::with_initial_path(&path))));
+        rsx! { App {} }
+    }
+
+    let path = path.to_string();
+    render(&mut VirtualDom::new_with_props(
+        RouteAt,
+        RouteAtProps { path },
+    ))
+}
+
+#[test]
+fn component() {
+    fn app() -> Element {
+        rsx! {
+            Component { title: "Profile", p { "Body" } }
+        }
+    }
+
+    insta::assert_snapshot!(render(&mut VirtualDom::new(app)));
+}
+
+#[test]
+fn component_default_title() {
+    fn app() -> Element {
+        rsx! {
+            Component { p { "Body" } }
+        }
+    }
+
+    insta::assert_snapshot!(render(&mut VirtualDom::new(app)));
+}
+
+#[test]
+fn action_card() {
+    fn app() -> Element {
+        rsx! {
+            ActionCard {
+                title: "Delete post?",
+                action_label: "Delete",
+                on_action: |_| {},
+                on_dismiss: |_| {},
+                "This cannot be undone."
+            }
+        }
+    }
+
+    insta::assert_snapshot!(render(&mut VirtualDom::new(app)));
+}
+
+#[test]
+fn action_card_without_optional_props() {
+    fn app() -> Element {
+        rsx! {
+            ActionCard { title: "Saved", on_action: |_| {} }
+        }
+    }
+
+    insta::assert_snapshot!(render(&mut VirtualDom::new(app)));
+}
+
+#[test]
+fn route_home() {
+    insta::assert_snapshot!(render_route("/"));
+}
+
+#[test]
+fn route_about() {
+    insta::assert_snapshot!(render_route("/about"));
+}
+
+#[test]
+fn route_blog_post() {
+    insta::assert_snapshot!(render_route("/blog/7"));
+}
+
+#[test]
+fn route_login() {
+    insta::assert_snapshot!(render_route("/login?redirect=%2Fadmin%2Fdashboard"));
+}
+
+/// `AdminGuard` renders nothing of the dashboard until the user is known.
+#[test]
+fn route_admin_dashboard() {
+    insta::assert_snapshot!(render_route("/admin/dashboard"));
+}
+
+#[test]
+fn route_not_found() {
+    insta::assert_snapshot!(render_route("/no/such/page"));
+}
+
+#[test]
+fn server_component() {
+    insta::assert_snapshot!(render(&mut VirtualDom::new(ServerComponent)));
+}
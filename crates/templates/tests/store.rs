@@ -0,0 +1,200 @@
+//! Checks the reducer on its own, and that components reading the store
+//! through selectors re-render only when their slice changes. Each component
+//! under test logs its name when it renders.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::dioxus_core::NoOpMutations;
+use dioxus::prelude::*;
+use dioxus_fullstack_templates::store::{
+    use_app_state_provider, use_filter, use_remaining, use_visible_todos, Action, AppState, Filter,
+    State, Theme, TodoApp, DARK_MODE, THEME,
+};
+
+fn state_with(titles: &[&str]) -> State {
+    let mut state = State::default();
+    for title in titles {
+        state.reduce(Action::Add(title.to_string()));
+    }
+    state
+}
+
+fn titles(state: &State) -> Vec<(&str, bool)> {
+    state
+        .todos
+        .iter()
+        .map(|todo| (todo.title.as_str(), todo.done))
+        .collect()
+}
+
+#[test]
+fn reducer_applies_actions() {
+    let mut state = state_with(&["milk", "  ", " eggs "]);
+    assert_eq!(titles(&state), [("milk", false), ("eggs", false)]);
+
+    let eggs = state.todos[1].id;
+    state.reduce(Action::Toggle(eggs));
+    state.reduce(Action::Add("bread".to_string()));
+    state.reduce(Action::Rename {
+        id: eggs,
+        title: "six eggs".to_string(),
+    });
+    assert_eq!(
+        titles(&state),
+        [("milk", false), ("six eggs", true), ("bread", false)]
+    );
+
+    state.reduce(Action::ClearDone);
+    state.reduce(Action::Remove(state.todos[0].id));
+    state.reduce(Action::SetFilter(Filter::Done));
+    assert_eq!(titles(&state), [("bread", false)]);
+    assert_eq!(state.filter, Filter::Done);
+
+    // Ids are never reused, even after a removal.
+    state.reduce(Action::Add("jam".to_string()));
+    let ids: Vec<u32> = state.todos.iter().map(|todo| todo.id).collect();
+    assert_eq!(ids, [3, 4]);
+}
+
+#[test]
+fn rename_trims_and_keeps_the_title_when_blank() {
+    let mut state = state_with(&["milk"]);
+    let milk = state.todos[0].id;
+
+    state.reduce(Action::Rename {
+        id: milk,
+        title: "   ".to_string(),
+    });
+    assert_eq!(titles(&state), [("milk", false)]);
+
+    state.reduce(Action::Rename {
+        id: milk,
+        title: " oat milk ".to_string(),
+    });
+    assert_eq!(titles(&state), [("oat milk", false)]);
+}
+
+/// The names of the components that rendered, in order.
+type Renders = Rc<RefCell<Vec<&'static str>>>;
+
+fn rendered(name: &'static str) {
+    consume_context::<Renders>().borrow_mut().push(name);
+}
+
+fn app() -> Element {
+    let app = use_app_state_provider(|| state_with(&["milk", "eggs"]));
+    use_hook(|| consume_context::<Rc<RefCell<Option<AppState>>>>().replace(Some(app)));
+    rsx! {
+        Remaining {}
+        CurrentFilter {}
+        Visible {}
+    }
+}
+
+#[component]
+fn Remaining() -> Element {
+    let remaining = use_remaining();
+    rendered("remaining");
+    rsx! { "{remaining}" }
+}
+
+#[component]
+fn CurrentFilter() -> Element {
+    let filter = use_filter();
+    rendered("filter");
+    rsx! { "{filter:?}" }
+}
+
+#[component]
+fn Visible() -> Element {
+    let todos = use_visible_todos();
+    rendered("visible");
+    rsx! { "{todos.len()}" }
+}
+
+struct Harness {
+    dom: VirtualDom,
+    app: AppState,
+    renders: Renders,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let renders = Renders::default();
+        let slot = Rc::new(RefCell::new(None::<AppState>));
+        let mut dom = VirtualDom::new(app)
+            .with_root_context(renders.clone())
+            .with_root_context(slot.clone());
+        dom.rebuild_in_place();
+        let app = slot.borrow().expect("the app provides its state");
+        renders.borrow_mut().clear();
+        Harness { dom, app, renders }
+    }
+
+    /// Dispatches `action` and returns what re-rendered because of it.
+    fn dispatch(&mut self, action: Action) -> Vec<&'static str> {
+        self.dom.in_runtime(|| self.app.dispatch(action));
+        self.dom.render_immediate(&mut NoOpMutations);
+        self.renders.take()
+    }
+}
+
+#[test]
+fn components_render_only_when_their_slice_changes() {
+    let mut harness = Harness::new();
+
+    assert_eq!(
+        harness.dispatch(Action::Add("bread".to_string())),
+        ["remaining", "visible"]
+    );
+    assert_eq!(
+        harness.dispatch(Action::SetFilter(Filter::Done)),
+        ["filter", "visible"]
+    );
+    // Toggling changes the count, and what the `Done` filter shows.
+    assert_eq!(
+        harness.dispatch(Action::Toggle(1)),
+        ["remaining", "visible"]
+    );
+    // Renaming a todo the filter hides changes nothing that is shown.
+    assert!(harness
+        .dispatch(Action::Rename {
+            id: 2,
+            title: "six eggs".to_string(),
+        })
+        .is_empty());
+    assert!(harness.dispatch(Action::SetFilter(Filter::Done)).is_empty());
+    assert!(harness.dispatch(Action::Toggle(99)).is_empty());
+}
+
+#[test]
+fn todo_app_starts_empty() {
+    let mut dom = VirtualDom::new(TodoApp);
+    dom.rebuild_in_place();
+    let html = dioxus::ssr::render(&dom);
+    assert!(html.contains("0 items left"), "{html}");
+    assert!(html.contains("Dark mode"), "{html}");
+}
+
+#[test]
+fn globals_belong_to_each_app() {
+    fn app() -> Element {
+        rsx! { "{DARK_MODE}" }
+    }
+
+    let mut first = VirtualDom::new(app);
+    first.rebuild_in_place();
+    let second = {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        dom
+    };
+
+    first.in_runtime(|| *THEME.write() = Theme::Dark);
+    first.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus::ssr::render(&first), "true");
+    assert_eq!(dioxus::ssr::render(&second), "false");
+    assert_eq!(first.in_runtime(|| THEME.cloned()), Theme::Dark);
+    assert_eq!(second.in_runtime(|| THEME.cloned()), Theme::Light);
+}
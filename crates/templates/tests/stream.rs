@@ -0,0 +1,74 @@
+//! Reads `stream_words` from a local server the way the component does:
+//! text as it arrives, cut into lines.
+#![cfg(feature = "server")]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use dioxus_fullstack_templates::stream::Chunk;
+use serde_json::json;
+
+/// Posts to the stream and collects its chunks, each with the time it came in.
+async fn read_stream(text: &str, from: usize) -> Vec<(Chunk, Instant)> {
+    let server = common::spawn_server().await;
+    let mut response = reqwest::Client::new()
+        .post(format!("{}/api/stream/words", server.url))
+        .json(&json!({ "text": text, "from": from }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let mut chunks = Vec::new();
+    let mut buffer = String::new();
+    while let Some(bytes) = response.chunk().await.unwrap() {
+        buffer.push_str(std::str::from_utf8(&bytes).unwrap());
+        while let Some(end) = buffer.find('\n') {
+            let line: String = buffer.drain(..=end).collect();
+            chunks.push((serde_json::from_str(&line).unwrap(), Instant::now()));
+        }
+    }
+    assert_eq!(buffer, "", "the stream ends with a full line");
+    chunks
+}
+
+#[tokio::test]
+async fn streams_every_word_in_order() {
+    let chunks = read_stream("one two three four", 0).await;
+
+    let received: Vec<_> = chunks
+        .iter()
+        .map(|(chunk, _)| (chunk.index, chunk.total, chunk.text.as_str()))
+        .collect();
+    assert_eq!(
+        received,
+        [
+            (0, 4, "one "),
+            (1, 4, "two "),
+            (2, 4, "three "),
+            (3, 4, "four")
+        ]
+    );
+
+    // Words arrive as they are produced, not all at once at the end.
+    let (_, first) = chunks[0];
+    let (_, last) = chunks[3];
+    assert!(last - first >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn resumes_from_an_index() {
+    let chunks = read_stream("one two three four", 2).await;
+
+    let received: Vec<_> = chunks
+        .iter()
+        .map(|(chunk, _)| (chunk.index, chunk.text.as_str()))
+        .collect();
+    assert_eq!(received, [(2, "three "), (3, "four")]);
+}
+
+#[tokio::test]
+async fn empty_text_streams_nothing() {
+    assert!(read_stream("", 0).await.is_empty());
+}
@@ -0,0 +1,78 @@
+//! Requests the streamed dashboard and records which chunk of the body each
+//! part arrives in, and where in the page. Only the order is checked, not
+//! how long anything took.
+#![cfg(feature = "server")]
+
+mod common;
+
+use futures_util::StreamExt;
+use reqwest::StatusCode;
+
+/// What the server sent, and the number of the body chunk each of `markers`
+/// first showed up in.
+async fn stream_page(path: &str, markers: &[&str]) -> (String, Vec<usize>) {
+    let server = common::spawn_server().await;
+    let response = reqwest::get(format!("{}{path}", server.url)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut html = String::new();
+    let mut arrived = vec![None; markers.len()];
+    let mut body = response.bytes_stream().enumerate();
+    while let Some((chunk_number, chunk)) = body.next().await {
+        html.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+        for (marker, arrived) in markers.iter().zip(&mut arrived) {
+            if arrived.is_none() && html.contains(marker) {
+                *arrived = Some(chunk_number);
+            }
+        }
+    }
+
+    let arrived = arrived
+        .into_iter()
+        .zip(markers)
+        .map(|(arrived, marker)| arrived.unwrap_or_else(|| panic!("{marker} never came")))
+        .collect();
+    (html, arrived)
+}
+
+#[tokio::test]
+async fn shell_arrives_before_the_slow_section() {
+    let sections = ["87 orders", "Version 2.0 is out", "815 this week"];
+    let (html, arrived) = stream_page("/dashboard", &["<h1>Dashboard</h1>", sections[0]]).await;
+
+    // The shell does not wait for any data: it comes in an earlier chunk
+    // than the report, the slowest section.
+    let [shell, report] = arrived[..] else {
+        unreachable!()
+    };
+    assert!(shell < report, "shell in chunk {shell}, report in {report}");
+
+    // Until then the shell shows a skeleton where each section goes.
+    let shell_html = &html[..html.find("</main>").unwrap()];
+    assert_eq!(shell_html.matches(r#"class="skeleton""#).count(), 3);
+    assert!(!shell_html.contains("87 orders"));
+
+    // Sections are appended as they resolve, not in page order: the report
+    // is first on the page but last in the body.
+    let sales = shell_html.find("Sales").unwrap();
+    assert!(sales < shell_html.find("Headlines").unwrap());
+    let [report, headlines, visitors] = sections.map(|content| html.find(content).unwrap());
+    assert!(
+        shell_html.len() < headlines && headlines < visitors && visitors < report,
+        "headlines at {headlines}, visitors at {visitors}, report at {report}"
+    );
+}
+
+#[tokio::test]
+async fn each_section_brings_its_hydration_data() {
+    let (html, _) = stream_page("/dashboard", &["</html>"]).await;
+
+    // The shell carries the data rendered so far, and each section its own,
+    // so the client does not call the server functions again.
+    assert!(html.contains("window.initial_dioxus_hydration_data="));
+    for content in ["87 orders", "Version 2.0 is out", "815 this week"] {
+        let section = &html[html.find(content).unwrap()..];
+        let script = &section[..section.find("</script>").unwrap()];
+        assert!(script.contains("window.dx_hydrate(["), "{script}");
+    }
+}
@@ -0,0 +1,119 @@
+// Dioxus 0.7 Admin Template
+//
+// The `#[nest("/admin")]` routes in `route.rs` render inside `AdminGuard`,
+// which sends signed-out visitors to `Route::Login` and shows a 403 page to
+// signed-in users without the admin role. The guard only decides what the UI
+// shows: each server function here checks the role again with
+// `require_role`, because anyone can call it over HTTP directly.
+use dioxus::prelude::*;
+
+use crate::auth::{use_current_user, Role, User};
+use crate::error::AppError;
+use crate::route::Route;
+
+#[cfg(feature = "server")]
+use crate::auth::require_role;
+#[cfg(feature = "server")]
+use crate::crud::db::{database_error, Db};
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::Extension;
+#[cfg(feature = "server")]
+use tower_sessions::Session;
+
+/// Every account, for admins only.
+#[get("/api/admin/users", db: Extension<Db>, session: Session)]
+pub async fn list_users() -> Result<Vec<User>, AppError> {
+    require_role(&session, &db, Role::Admin).await?;
+
+    sqlx::query_as("SELECT id, email, role FROM users ORDER BY id")
+        .fetch_all(&*db)
+        .await
+        .map_err(database_error)
+}
+
+/// Changes a user's role, for admins only.
+#[post("/api/admin/role", db: Extension<Db>, session: Session)]
+pub async fn set_role(id: i64, role: Role) -> Result<User, AppError> {
+    require_role(&session, &db, Role::Admin).await?;
+
+    sqlx::query_as("UPDATE users SET role = ? WHERE id = ? RETURNING id, email, role")
+        .bind(role)
+        .bind(id)
+        .fetch_one(&*db)
+        .await
+        .map_err(database_error)
+}
+
+/// Layout for the admin routes: renders them only for admins.
+#[component]
+pub fn AdminGuard() -> Element {
+    let current = use_current_user();
+    let route = use_route::<Route>();
+    let navigator = use_navigator();
+
+    // Navigate from an effect, not while rendering. The login page sends the
+    // user back here once they are signed in.
+    use_effect(move || {
+        if !current.loading() && current.user().is_none() {
+            navigator.replace(Route::Login {
+                redirect: route.to_string(),
+            });
+        }
+    });
+
+    match current.user() {
+        Some(user) if user.role == Role::Admin => rsx! { Outlet::<Route> {} },
+        Some(_) => rsx! { Forbidden {} },
+        None => rsx! { p { "Loading..." } },
+    }
+}
+
+#[component]
+pub fn Forbidden() -> Element {
+    rsx! {
+        div { class: "forbidden",
+            h1 { "403 - Forbidden" }
+            p { "You need the admin role to see this page." }
+            Link { to: Route::Home {}, "Home" }
+        }
+    }
+}
+
+#[component]
+pub fn AdminDashboard() -> Element {
+    let navigator = use_navigator();
+    let mut users = use_resource(list_users);
+    let mut set_role = use_action(set_role);
+
+    use_effect(move || {
+        if set_role.value().is_some() {
+            users.restart();
+        }
+    });
+
+    rsx! {
+        div { class: "admin-dashboard",
+            h1 { "Admin Dashboard" }
+            match &*users.read() {
+                Some(Ok(users)) => rsx! {
+                    ul {
+                        for user in users.iter().cloned() {
+                            li { key: "{user.id}",
+                                "{user.email} ({user.role:?})"
+                                if user.role == Role::Admin {
+                                    button { onclick: move |_| set_role.call(user.id, Role::User), "Make user" }
+                                } else {
+                                    button { onclick: move |_| set_role.call(user.id, Role::Admin), "Make admin" }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! { p { class: "error", "Error: {e}" } },
+                None => rsx! { p { "Loading..." } },
+            }
+            // `replace` swaps the current history entry, so "back" skips the dashboard.
+            button { onclick: move |_| { navigator.replace(Route::Home {}); }, "Exit admin" }
+        }
+    }
+}
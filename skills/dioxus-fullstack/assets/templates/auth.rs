@@ -0,0 +1,319 @@
+// Dioxus 0.7 Auth Template
+//
+// Email and password accounts with a cookie session. Passwords are hashed
+// with argon2; the session only stores the user's id, in tower-sessions'
+// in-memory store (swap in a persistent store to survive restarts). Users
+// live in the `users` table from `migrations/0002_create_users.sql`.
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::Extension;
+#[cfg(feature = "server")]
+use tower_sessions::Session;
+
+#[cfg(feature = "server")]
+use self::server::{hash_password, verify_password, USER_ID};
+#[cfg(feature = "server")]
+use crate::crud::db::{database_error, Db};
+
+/// A signed-in user, as the client sees it. The password hash never leaves
+/// the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct User {
+    pub id: i64,
+    pub email: String,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "lowercase"))]
+pub enum Role {
+    User,
+    Admin,
+}
+
+/// Shortest password `register` accepts.
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+// Fixed paths, so other clients (and the tests) can call these over HTTP.
+// `db` and `session` are server-only arguments extracted from the request.
+
+/// Creates an account and signs it in.
+#[post("/api/auth/register", db: Extension<Db>, session: Session)]
+pub async fn register(email: String, password: String) -> Result<User, AppError> {
+    let email = email.trim().to_string();
+    if !email.contains('@') {
+        return Err(AppError::validation("email", "enter a valid email address"));
+    }
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AppError::validation(
+            "password",
+            "the password is too short",
+        ));
+    }
+
+    let password_hash = hash_password(password).await?;
+    let user: User = sqlx::query_as(
+        "INSERT INTO users (email, password_hash) VALUES (?, ?) RETURNING id, email, role",
+    )
+    .bind(&email)
+    .bind(password_hash)
+    .fetch_one(&*db)
+    .await
+    .map_err(database_error)?;
+
+    sign_in(&session, &user).await?;
+    Ok(user)
+}
+
+#[post("/api/auth/login", db: Extension<Db>, session: Session)]
+pub async fn login(email: String, password: String) -> Result<User, AppError> {
+    let row: Option<(i64, String, Role, String)> =
+        sqlx::query_as("SELECT id, email, role, password_hash FROM users WHERE email = ?")
+            .bind(email.trim())
+            .fetch_optional(&*db)
+            .await
+            .map_err(database_error)?;
+
+    // Verify against something even for unknown emails, so the response time
+    // does not reveal which emails have accounts.
+    let hash = row.as_ref().map(|(.., hash)| hash.clone());
+    let valid = verify_password(password, hash).await?;
+
+    match row {
+        Some((id, email, role, _)) if valid => {
+            let user = User { id, email, role };
+            sign_in(&session, &user).await?;
+            Ok(user)
+        }
+        _ => Err(AppError::Unauthorized),
+    }
+}
+
+#[post("/api/auth/logout", session: Session)]
+pub async fn logout() -> Result<(), AppError> {
+    session.flush().await.map_err(AppError::internal)
+}
+
+/// The signed-in user, or `None`.
+#[get("/api/auth/me", db: Extension<Db>, session: Session)]
+pub async fn current_user() -> Result<Option<User>, AppError> {
+    session_user(&session, &db).await
+}
+
+/// The user behind `session`, for server functions that need one. A session
+/// whose account is gone is dropped.
+#[cfg(feature = "server")]
+pub async fn session_user(session: &Session, db: &Db) -> Result<Option<User>, AppError> {
+    let Some(id) = session
+        .get::<i64>(USER_ID)
+        .await
+        .map_err(AppError::internal)?
+    else {
+        return Ok(None);
+    };
+
+    let user = sqlx::query_as("SELECT id, email, role FROM users WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(database_error)?;
+    if user.is_none() {
+        session.flush().await.map_err(AppError::internal)?;
+    }
+    Ok(user)
+}
+
+/// The signed-in user if they have `role`: `Unauthorized` when signed out
+/// and `Forbidden` for any other role. Every server function behind a route guard calls this
+/// itself, since anyone can call it over HTTP without going through the UI.
+#[cfg(feature = "server")]
+pub async fn require_role(session: &Session, db: &Db, role: Role) -> Result<User, AppError> {
+    match session_user(session, db).await? {
+        Some(user) if user.role == role => Ok(user),
+        Some(_) => Err(AppError::Forbidden),
+        None => Err(AppError::Unauthorized),
+    }
+}
+
+#[cfg(feature = "server")]
+async fn sign_in(session: &Session, user: &User) -> Result<(), AppError> {
+    // A new session id on sign-in, so an id planted before it is worthless.
+    session.cycle_id().await.map_err(AppError::internal)?;
+    session
+        .insert(USER_ID, user.id)
+        .await
+        .map_err(AppError::internal)
+}
+
+/// Server-side pieces: the session layer `server::app` adds to the router, and
+/// password hashing.
+#[cfg(feature = "server")]
+pub mod server {
+    use std::sync::LazyLock;
+
+    use argon2::password_hash::rand_core::OsRng;
+    use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+    use argon2::Argon2;
+    use tower_sessions::cookie::SameSite;
+    use tower_sessions::{MemoryStore, SessionManagerLayer};
+
+    use crate::error::AppError;
+
+    /// Session key holding the signed-in user's id.
+    pub const USER_ID: &str = "user_id";
+
+    /// Cookie sessions in memory. Add it to the router with `.layer(...)`.
+    pub fn session_layer() -> SessionManagerLayer<MemoryStore> {
+        SessionManagerLayer::new(MemoryStore::default())
+            .with_same_site(SameSite::Lax)
+            // `dx serve` is plain HTTP; release builds are expected behind HTTPS.
+            .with_secure(!cfg!(debug_assertions))
+    }
+
+    /// Hashes on a blocking thread: argon2 is slow on purpose.
+    pub async fn hash_password(password: String) -> Result<String, AppError> {
+        tokio::task::spawn_blocking(move || {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+        })
+        .await
+        .map_err(AppError::internal)?
+        .map_err(AppError::internal)
+    }
+
+    /// Hash of an empty password, checked when the email is unknown. Made
+    /// once, on first use.
+    static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(b"", &salt)
+            .expect("hashing with the default parameters works")
+            .to_string()
+    });
+
+    /// Checks `password` against `hash`, or against a dummy hash when there
+    /// is none: one argon2 run either way, so both cases take as long.
+    pub async fn verify_password(password: String, hash: Option<String>) -> Result<bool, AppError> {
+        tokio::task::spawn_blocking(move || {
+            let hash = hash.as_deref().unwrap_or(&DUMMY_HASH);
+            let hash = PasswordHash::new(hash).map_err(AppError::internal)?;
+            Ok(Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok())
+        })
+        .await
+        .map_err(AppError::internal)?
+    }
+}
+
+/// The signed-in user, shared by every component below the provider.
+#[derive(Clone, Copy)]
+pub struct CurrentUser(Resource<Result<Option<User>, AppError>>);
+
+impl CurrentUser {
+    /// `None` while loading, when signed out, or if the check failed.
+    pub fn user(&self) -> Option<User> {
+        match &*self.0.read() {
+            Some(Ok(user)) => user.clone(),
+            _ => None,
+        }
+    }
+
+    /// Whether the first check is still running.
+    pub fn loading(&self) -> bool {
+        self.0.read().is_none()
+    }
+
+    /// Asks the server again, e.g. after signing in or out.
+    pub fn refresh(&mut self) {
+        self.0.restart();
+    }
+}
+
+/// Loads the current user and provides it to every component below. Call it
+/// once, high up: in `App` or the router's layout.
+pub fn use_current_user_provider() -> CurrentUser {
+    let user = use_resource(current_user);
+    use_context_provider(|| CurrentUser(user))
+}
+
+/// The current user from the nearest [`use_current_user_provider`].
+pub fn use_current_user() -> CurrentUser {
+    use_context()
+}
+
+#[component]
+pub fn LoginForm() -> Element {
+    let mut current = use_current_user();
+    let mut email = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut login = use_action(login);
+    // `use_action` type-erases errors; turn them back into `AppError`.
+    let error = login.value().and_then(Result::err).map(AppError::from);
+
+    use_effect(move || {
+        if let Some(Ok(_)) = login.value() {
+            current.refresh();
+        }
+    });
+
+    rsx! {
+        form {
+            class: "login-form",
+            onsubmit: move |e: FormEvent| {
+                e.prevent_default();
+                login.call(email(), password());
+            },
+            input {
+                r#type: "email",
+                value: "{email}",
+                oninput: move |e| email.set(e.value()),
+                placeholder: "Email",
+            }
+            input {
+                r#type: "password",
+                value: "{password}",
+                oninput: move |e| password.set(e.value()),
+                placeholder: "Password",
+            }
+            button { r#type: "submit", disabled: login.pending(), "Sign in" }
+            match error {
+                Some(AppError::Unauthorized) => rsx! { p { class: "error", "Wrong email or password" } },
+                Some(err) => rsx! { p { class: "error", "{err}" } },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+/// Shows who is signed in, with a sign-out button.
+#[component]
+pub fn UserMenu() -> Element {
+    let mut current = use_current_user();
+
+    let Some(user) = current.user() else {
+        return rsx! {};
+    };
+    rsx! {
+        div { class: "user-menu",
+            span { "{user.email}" }
+            button {
+                onclick: move |_| async move {
+                    if logout().await.is_ok() {
+                        current.refresh();
+                    }
+                },
+                "Sign out"
+            }
+        }
+    }
+}
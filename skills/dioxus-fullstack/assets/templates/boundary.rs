@@ -0,0 +1,106 @@
+// Dioxus 0.7 Error and Suspense Boundary Template
+//
+// Two boundaries catch what a component hands up instead of rendering:
+// - `SuspenseBoundary` shows its fallback, here a skeleton of the page, while
+//   a component below it waits for data. The server streams pages (see
+//   `streaming.rs`), so a first visit gets the skeleton at once too, and the
+//   post replaces it in the same response once it is loaded. On client
+//   navigation the skeleton shows until the request returns.
+// - `ErrorBoundary` shows its fallback when a component below it returns an
+//   error with `?`. `AppErrorBoundary` wraps the router's `Outlet` in
+//   `route.rs`, so every page gets the same error screen and a retry button.
+use dioxus::prelude::*;
+
+use crate::crud::get_post;
+use crate::error::AppError;
+
+/// Catches the errors of everything inside and shows them instead, with a
+/// button that mounts the children again, which runs their requests again.
+#[component]
+pub fn AppErrorBoundary(children: Element) -> Element {
+    // A new key is a new boundary, without the old error or children.
+    let mut attempt = use_signal(|| 0);
+
+    rsx! {
+        ErrorBoundary {
+            key: "{attempt}",
+            handle_error: move |errors: ErrorContext| {
+                let error = errors.error().map(AppError::from).unwrap_or(AppError::Internal);
+                rsx! {
+                    ErrorFallback { error, on_retry: move |_| attempt += 1 }
+                }
+            },
+            {children}
+        }
+    }
+}
+
+/// What the user sees instead of a page that failed.
+#[component]
+fn ErrorFallback(error: AppError, on_retry: EventHandler) -> Element {
+    let (title, hint) = match &error {
+        AppError::NotFound => ("Not found", "It may have been moved or deleted."),
+        AppError::Unauthorized | AppError::Forbidden => {
+            ("Not allowed", "Sign in with an account that may see this.")
+        }
+        _ => ("Something went wrong", "This may be a hiccup. Try again."),
+    };
+
+    rsx! {
+        div { class: "error-fallback", role: "alert",
+            h1 { "{title}" }
+            p { "{hint}" }
+            p { class: "error-detail", "{error}" }
+            button { onclick: move |_| on_retry.call(()), "Try again" }
+        }
+    }
+}
+
+/// Grey bars in the shape of text, shown while it loads. Style `.skeleton`
+/// with a pulsing background.
+#[component]
+pub fn Skeleton(#[props(default = 3)] lines: usize) -> Element {
+    rsx! {
+        div { class: "skeleton", aria_busy: "true",
+            for line in 0..lines {
+                div { key: "{line}", class: "skeleton-line" }
+            }
+        }
+    }
+}
+
+/// A post page: a skeleton while the post loads, then the post.
+#[component]
+pub fn PostPage(id: ReadSignal<i64>) -> Element {
+    rsx! {
+        SuspenseBoundary {
+            fallback: |_| rsx! {
+                article { class: "post",
+                    div { class: "skeleton-title" }
+                    Skeleton { lines: 6 }
+                }
+            },
+            PostBody { id }
+        }
+    }
+}
+
+#[component]
+fn PostBody(id: ReadSignal<i64>) -> Element {
+    // The first `?` suspends until the request has run on the server, the
+    // second while it runs again on the client, e.g. for another `id`. The
+    // last hands a failed request to the nearest `ErrorBoundary`.
+    let post = use_server_future(move || {
+        let id = id();
+        async move { get_post(id).await }
+    })?
+    .suspend()?
+    .cloned()?;
+
+    rsx! {
+        article { class: "post",
+            h1 { "{post.title}" }
+            p { "{post.body}" }
+        }
+    }
+}
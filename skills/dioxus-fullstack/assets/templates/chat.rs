@@ -0,0 +1,364 @@
+// Dioxus 0.7 WebSocket Chat Template
+//
+// Rooms of people sending each other messages. The socket is a plain axum
+// route next to the app (`server::routes`), and every room is a tokio
+// broadcast channel in a shared `Hub`. Both directions carry JSON: the client
+// sends `ClientMessage`s and gets `ServerMessage`s back, including who is in
+// the room whenever that changes.
+use dioxus::fullstack::{
+    ClientRequest, FromResponse, IntoRequest, Method, WebSocketOptions, Websocket,
+};
+use dioxus::prelude::*;
+use futures_util::future::{self, Either};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Longest room or member name the server accepts, in characters.
+pub const MAX_NAME_LEN: usize = 32;
+/// Longest message the server passes on, in characters.
+pub const MAX_MESSAGE_LEN: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Say { text: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Said {
+        from: String,
+        text: String,
+    },
+    Joined {
+        name: String,
+    },
+    Left {
+        name: String,
+    },
+    /// Everyone in the room, sorted, sent after every join and leave.
+    Presence {
+        members: Vec<String>,
+    },
+}
+
+/// The query string of the socket's URL: `/ws/chat/{room}?name=...`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Join {
+    pub name: String,
+}
+
+/// Room names go into the URL path, so keep them to letters, digits and `-`.
+pub fn valid_room(room: &str) -> bool {
+    !room.is_empty()
+        && room.len() <= MAX_NAME_LEN
+        && room.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(feature = "server")]
+pub mod server {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::{Arc, Mutex};
+
+    use dioxus::logger::tracing;
+    use dioxus::server::axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+    use dioxus::server::axum::extract::{Path, Query};
+    use dioxus::server::axum::http::StatusCode;
+    use dioxus::server::axum::response::{IntoResponse, Response};
+    use dioxus::server::axum::routing::get;
+    use dioxus::server::axum::{Extension, Router};
+    use tokio::sync::broadcast::{self, error::RecvError};
+
+    use super::{valid_room, ClientMessage, Join, ServerMessage, MAX_MESSAGE_LEN, MAX_NAME_LEN};
+
+    /// Messages a room holds for a member who is slow to read them. Whoever
+    /// falls further behind misses the oldest ones.
+    const ROOM_CAPACITY: usize = 64;
+
+    /// The chat route. Merge it into the app's router, with the hub it uses:
+    /// `.merge(chat::server::routes()).layer(Extension(Hub::default()))`.
+    pub fn routes() -> Router {
+        Router::new().route("/ws/chat/{room}", get(connect))
+    }
+
+    /// Every open room. Clones share the rooms.
+    #[derive(Clone, Default)]
+    pub struct Hub {
+        rooms: Arc<Mutex<HashMap<String, Room>>>,
+    }
+
+    struct Room {
+        sender: broadcast::Sender<ServerMessage>,
+        /// Connections per name: one person can have the room open twice.
+        members: BTreeMap<String, usize>,
+    }
+
+    impl Room {
+        fn presence(&self) -> ServerMessage {
+            ServerMessage::Presence {
+                members: self.members.keys().cloned().collect(),
+            }
+        }
+    }
+
+    impl Hub {
+        /// Adds `name` to `room`, opening it if needed, and returns what the
+        /// room says from now on, starting with the join itself.
+        pub fn join(&self, room: &str, name: &str) -> broadcast::Receiver<ServerMessage> {
+            let mut rooms = self.rooms.lock().unwrap();
+            let room = rooms.entry(room.to_string()).or_insert_with(|| Room {
+                sender: broadcast::channel(ROOM_CAPACITY).0,
+                members: BTreeMap::new(),
+            });
+            let receiver = room.sender.subscribe();
+
+            let connections = room.members.entry(name.to_string()).or_default();
+            *connections += 1;
+            if *connections == 1 {
+                // The receiver above keeps the channel open: sending cannot fail.
+                let _ = room.sender.send(ServerMessage::Joined {
+                    name: name.to_string(),
+                });
+                let _ = room.sender.send(room.presence());
+            }
+            receiver
+        }
+
+        /// Removes one connection of `name` from `room`, and the room once
+        /// nobody is left.
+        pub fn leave(&self, room_name: &str, name: &str) {
+            let mut rooms = self.rooms.lock().unwrap();
+            let Some(room) = rooms.get_mut(room_name) else {
+                return;
+            };
+            let Some(connections) = room.members.get_mut(name) else {
+                return;
+            };
+
+            *connections -= 1;
+            if *connections > 0 {
+                return;
+            }
+            room.members.remove(name);
+            if room.members.is_empty() {
+                rooms.remove(room_name);
+                return;
+            }
+            let _ = room.sender.send(ServerMessage::Left {
+                name: name.to_string(),
+            });
+            let _ = room.sender.send(room.presence());
+        }
+
+        /// Sends `message` to everyone in `room`.
+        pub fn broadcast(&self, room: &str, message: ServerMessage) {
+            if let Some(room) = self.rooms.lock().unwrap().get(room) {
+                let _ = room.sender.send(message);
+            }
+        }
+
+        /// Who is in `room`, sorted.
+        pub fn members(&self, room: &str) -> Vec<String> {
+            self.rooms
+                .lock()
+                .unwrap()
+                .get(room)
+                .map(|room| room.members.keys().cloned().collect())
+                .unwrap_or_default()
+        }
+    }
+
+    /// Upgrades to a WebSocket once the room and the name are acceptable.
+    async fn connect(
+        upgrade: WebSocketUpgrade,
+        Path(room): Path<String>,
+        Query(Join { name }): Query<Join>,
+        Extension(hub): Extension<Hub>,
+    ) -> Response {
+        let name = name.trim().to_string();
+        if !valid_room(&room) {
+            return (StatusCode::NOT_FOUND, "no such room").into_response();
+        }
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("names are 1 to {MAX_NAME_LEN} characters"),
+            )
+                .into_response();
+        }
+        upgrade.on_upgrade(move |socket| session(socket, hub, room, name))
+    }
+
+    /// Passes messages between one socket and its room until either side
+    /// is done.
+    async fn session(mut socket: WebSocket, hub: Hub, room: String, name: String) {
+        let mut events = hub.join(&room, &name);
+
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let json = serde_json::to_string(&event).expect("a message serializes");
+                        if socket.send(Message::Text(json.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!(%room, %name, missed, "chat client fell behind");
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                incoming = socket.recv() => {
+                    // Browsers send text frames; the native client sends binary.
+                    let message: Option<ClientMessage> = match incoming {
+                        Some(Ok(Message::Text(text))) => serde_json::from_str(&text).ok(),
+                        Some(Ok(Message::Binary(bytes))) => serde_json::from_slice(&bytes).ok(),
+                        Some(Ok(_)) => continue,
+                        Some(Err(_)) | None => break,
+                    };
+                    match message {
+                        Some(ClientMessage::Say { text }) => {
+                            let text = text.trim();
+                            if !text.is_empty() && text.chars().count() <= MAX_MESSAGE_LEN {
+                                let from = name.clone();
+                                let text = text.to_string();
+                                hub.broadcast(&room, ServerMessage::Said { from, text });
+                            }
+                        }
+                        None => tracing::debug!(%room, %name, "ignoring a malformed message"),
+                    }
+                }
+            }
+        }
+
+        hub.leave(&room, &name);
+    }
+}
+
+/// Opens the chat socket with the client server functions use: a browser
+/// WebSocket on the web, and a connection to the server's URL elsewhere.
+pub async fn connect(
+    room: &str,
+    name: &str,
+) -> Result<Websocket<ClientMessage, ServerMessage>, ServerFnError> {
+    let join = Join {
+        name: name.to_string(),
+    };
+    let request = ClientRequest::new(Method::GET, format!("/ws/chat/{room}"), &join);
+    let upgrading = WebSocketOptions::new()
+        .into_request(request)
+        .await
+        .map_err(ServerFnError::from)?;
+    Websocket::from_response(upgrading).await
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatStatus {
+    Connecting,
+    Open,
+    Closed(String),
+}
+
+/// A chat room: the conversation, who is here, and a box to write in.
+#[component]
+pub fn ChatRoom(room: String, name: String) -> Element {
+    let mut log = use_signal(Vec::<ServerMessage>::new);
+    let mut members = use_signal(Vec::<String>::new);
+    let mut status = use_signal(|| ChatStatus::Connecting);
+    let mut draft = use_signal(String::new);
+
+    // The coroutine owns the socket: it forwards what the form sends it and
+    // fills the signals with what arrives. It stops, closing the socket,
+    // when the component unmounts.
+    let chat = use_coroutine(move |mut outgoing: UnboundedReceiver<ClientMessage>| {
+        let (room, name) = (room.clone(), name.clone());
+        async move {
+            let socket = match connect(&room, &name).await {
+                Ok(socket) => socket,
+                Err(err) => {
+                    status.set(ChatStatus::Closed(err.to_string()));
+                    return;
+                }
+            };
+            status.set(ChatStatus::Open);
+
+            let send = async {
+                while let Some(message) = outgoing.next().await {
+                    socket.send(message).await?;
+                }
+                Ok(())
+            };
+            let receive = async {
+                loop {
+                    match socket.recv().await {
+                        Ok(ServerMessage::Presence { members: now }) => members.set(now),
+                        Ok(message) => log.push(message),
+                        Err(err) => return Err(err),
+                    }
+                }
+            };
+
+            let ended = match future::select(std::pin::pin!(send), std::pin::pin!(receive)).await {
+                Either::Left((ended, _)) | Either::Right((ended, _)) => ended,
+            };
+            let reason = match ended {
+                Ok(()) => "closed".to_string(),
+                Err(err) => err.to_string(),
+            };
+            status.set(ChatStatus::Closed(reason));
+        }
+    });
+
+    let send = move |event: FormEvent| {
+        event.prevent_default();
+        let text = draft.take();
+        if !text.trim().is_empty() {
+            chat.send(ClientMessage::Say { text });
+        }
+    };
+
+    rsx! {
+        div { class: "chat",
+            aside { class: "members",
+                h2 { "Here now" }
+                ul {
+                    for member in members() {
+                        li { key: "{member}", "{member}" }
+                    }
+                }
+            }
+            main {
+                if let ChatStatus::Closed(reason) = status() {
+                    p { class: "error", "Disconnected: {reason}" }
+                }
+                ul { class: "log",
+                    for message in log() {
+                        match message {
+                            ServerMessage::Said { from, text } => rsx! {
+                                li { strong { "{from}: " } "{text}" }
+                            },
+                            ServerMessage::Joined { name } => rsx! {
+                                li { class: "event", "{name} joined" }
+                            },
+                            ServerMessage::Left { name } => rsx! {
+                                li { class: "event", "{name} left" }
+                            },
+                            ServerMessage::Presence { .. } => rsx! {},
+                        }
+                    }
+                }
+                form { onsubmit: send,
+                    input {
+                        value: "{draft}",
+                        maxlength: MAX_MESSAGE_LEN,
+                        placeholder: "Say something",
+                        disabled: status() != ChatStatus::Open,
+                        oninput: move |event| draft.set(event.value()),
+                    }
+                    button { r#type: "submit", disabled: status() != ChatStatus::Open, "Send" }
+                }
+            }
+        }
+    }
+}
@@ -1,19 +1,59 @@
 // Dioxus 0.7 Component Template
 use dioxus::prelude::*;
 
+/// Props are plain owned data. Dioxus compares them with `PartialEq` to skip
+/// re-rendering when the parent passes the same values again.
 #[derive(Props, Clone, PartialEq)]
 pub struct ComponentProps {
-    #[props(default = "".to_string())]
+    /// `into` lets callers pass `"literal"` or a `String`; `default` makes it optional.
+    #[props(default, into)]
     pub title: String,
     pub children: Element,
 }
 
+/// Usage: `Component { title: "Profile", p { "Body" } }`
 #[component]
-pub fn Component(cx: Scope<ComponentProps>) -> Element {
-    render! {
+pub fn Component(props: ComponentProps) -> Element {
+    rsx! {
         div { class: "component",
-            h2 { "{cx.props.title}" }
-            {cx.props.children}
+            h2 { "{props.title}" }
+            {props.children}
         }
     }
-}
\ No newline at end of file
+}
+
+/// The same pattern with inline props: `#[component]` generates the props
+/// struct from the arguments, so attributes go on the arguments themselves.
+///
+/// Usage: `ActionCard { title: "Delete?", on_action: move |_| delete(), "This cannot be undone." }`
+#[component]
+pub fn ActionCard(
+    #[props(into)] title: String,
+    #[props(default = "OK".to_string(), into)] action_label: String,
+    /// Required handler, called with the click that triggered it.
+    on_action: EventHandler<MouseEvent>,
+    /// `Option` handlers are optional props; the dismiss button only renders when one is passed.
+    on_dismiss: Option<EventHandler>,
+    /// Optional children: `None` when the caller passes no body.
+    children: Option<Element>,
+) -> Element {
+    rsx! {
+        div { class: "action-card",
+            header {
+                h3 { "{title}" }
+                if let Some(on_dismiss) = on_dismiss {
+                    button {
+                        class: "dismiss",
+                        aria_label: "Dismiss",
+                        onclick: move |_| on_dismiss.call(()),
+                        "×"
+                    }
+                }
+            }
+            if let Some(children) = children {
+                div { class: "body", {children} }
+            }
+            button { class: "action", onclick: move |evt| on_action.call(evt), "{action_label}" }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// Dioxus 0.7 Database Template
+//
+// CRUD server functions for `Post` on SQLite, which needs no database server:
+// `DATABASE_URL=sqlite://app.db` is a file next to the app. Migrations live in
+// `migrations/` at the crate root (next to Cargo.toml) and are compiled into
+// the server binary by `sqlx::migrate!()`.
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::Extension;
+
+#[cfg(feature = "server")]
+use self::db::{database_error, Db};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct Post {
+    pub id: i64,
+    pub title: String,
+    pub body: String,
+}
+
+/// What the client sends to create or update a post.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostInput {
+    pub title: String,
+    pub body: String,
+}
+
+// `db: Extension<Db>` is a server-only argument: it is not part of the
+// request the client sends, but extracted from the axum request on the server,
+// where `server::app` added the pool with `.layer(Extension(pool))`.
+
+#[server(db: Extension<Db>)]
+pub async fn list_posts() -> Result<Vec<Post>, AppError> {
+    sqlx::query_as("SELECT id, title, body FROM posts ORDER BY id")
+        .fetch_all(&*db)
+        .await
+        .map_err(database_error)
+}
+
+#[server(db: Extension<Db>)]
+pub async fn get_post(id: i64) -> Result<Post, AppError> {
+    sqlx::query_as("SELECT id, title, body FROM posts WHERE id = ?")
+        .bind(id)
+        .fetch_one(&*db)
+        .await
+        .map_err(database_error)
+}
+
+#[server(db: Extension<Db>)]
+pub async fn create_post(input: PostInput) -> Result<Post, AppError> {
+    sqlx::query_as("INSERT INTO posts (title, body) VALUES (?, ?) RETURNING id, title, body")
+        .bind(input.title)
+        .bind(input.body)
+        .fetch_one(&*db)
+        .await
+        .map_err(database_error)
+}
+
+#[server(db: Extension<Db>)]
+pub async fn update_post(id: i64, input: PostInput) -> Result<Post, AppError> {
+    // No row to return means no post with that id: `RowNotFound`, so
+    // `AppError::NotFound`.
+    sqlx::query_as("UPDATE posts SET title = ?, body = ? WHERE id = ? RETURNING id, title, body")
+        .bind(input.title)
+        .bind(input.body)
+        .bind(id)
+        .fetch_one(&*db)
+        .await
+        .map_err(database_error)
+}
+
+#[server(db: Extension<Db>)]
+pub async fn delete_post(id: i64) -> Result<(), AppError> {
+    let deleted = sqlx::query("DELETE FROM posts WHERE id = ?")
+        .bind(id)
+        .execute(&*db)
+        .await
+        .map_err(database_error)?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// The pool and the mapping from database errors to `AppError`s. Only the
+/// server has a database, so none of this is compiled into the client.
+#[cfg(feature = "server")]
+pub mod db {
+    use std::str::FromStr;
+
+    use sqlx::error::ErrorKind;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    use crate::error::{AppError, FORM_FIELD};
+
+    pub type Db = sqlx::SqlitePool;
+
+    /// Opens the database at `url` and applies pending migrations. Call it
+    /// once at server start and share the pool; it is cheap to clone.
+    pub async fn connect(url: &str) -> Result<Db, sqlx::Error> {
+        let options = SqliteConnectOptions::from_str(url)?
+            .create_if_missing(true)
+            .foreign_keys(true);
+
+        // Every connection to `sqlite::memory:` opens its own empty
+        // database, so an in-memory pool must keep exactly one connection.
+        let pool = if url.contains(":memory:") {
+            SqlitePoolOptions::new()
+                .max_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+        } else {
+            SqlitePoolOptions::new()
+        };
+        let pool = pool.connect_with(options).await?;
+
+        sqlx::migrate!().run(&pool).await?;
+        Ok(pool)
+    }
+
+    /// Maps a database error to the `AppError` the client can act on.
+    /// Unexpected errors are logged and sent as `AppError::Internal`, so SQL
+    /// and schema details stay on the server.
+    pub fn database_error(err: sqlx::Error) -> AppError {
+        let conflict = |message: &str| AppError::Conflict {
+            message: message.to_string(),
+        };
+        match &err {
+            sqlx::Error::RowNotFound => AppError::NotFound,
+            sqlx::Error::Database(db) => match db.kind() {
+                ErrorKind::UniqueViolation => conflict("already exists"),
+                ErrorKind::ForeignKeyViolation => conflict("still referenced"),
+                ErrorKind::CheckViolation | ErrorKind::NotNullViolation => {
+                    AppError::validation(FORM_FIELD, "invalid value")
+                }
+                _ => AppError::internal(&err),
+            },
+            _ => AppError::internal(&err),
+        }
+    }
+}
+
+/// Lists posts with a form to add one and a delete button per post.
+#[component]
+pub fn PostList() -> Element {
+    let mut posts = use_resource(list_posts);
+    let mut title = use_signal(String::new);
+    let mut create = use_action(create_post);
+    let mut delete = use_action(delete_post);
+
+    // Refetch the list whenever a create or delete finishes.
+    use_effect(move || {
+        if create.value().is_some() || delete.value().is_some() {
+            posts.restart();
+        }
+    });
+
+    rsx! {
+        div { class: "posts",
+            form {
+                onsubmit: move |e: FormEvent| {
+                    e.prevent_default();
+                    create.call(PostInput { title: title(), body: String::new() });
+                    title.set(String::new());
+                },
+                input { value: "{title}", oninput: move |e| title.set(e.value()), placeholder: "Title" }
+                button { r#type: "submit", disabled: create.pending(), "Add" }
+            }
+            if let Some(Err(e)) = create.value() {
+                p { class: "error", "Could not add the post: {e}" }
+            }
+
+            match &*posts.read() {
+                Some(Ok(posts)) => rsx! {
+                    ul {
+                        for post in posts.iter().cloned() {
+                            li { key: "{post.id}",
+                                "{post.title}"
+                                button { onclick: move |_| delete.call(post.id), "Delete" }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! { p { class: "error", "Error: {e}" } },
+                None => rsx! { p { "Loading..." } },
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// Dioxus 0.7 Error Template
+//
+// One error type for server functions, instead of stringly
+// `ServerFnError`s. Return `Result<T, AppError>` from a `#[server]` function:
+// the server answers with the variant's HTTP status and the variant itself as
+// the error details, and the client decodes it back into the same variant,
+// so components can match on it.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use dioxus::fullstack::{AsStatusCode, StatusCode};
+use dioxus::prelude::*;
+use dioxus::CapturedError;
+use serde::{Deserialize, Serialize};
+use validator::ValidationErrors;
+
+/// The `field` of validation errors about the input as a whole rather than
+/// one of its fields. Forms show it above the submit button.
+pub const FORM_FIELD: &str = "form";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AppError {
+    /// The input in `field` is not acceptable; `message` says why, for
+    /// display next to that input.
+    Validation {
+        field: String,
+        message: String,
+    },
+    NotFound,
+    /// Signed out.
+    Unauthorized,
+    /// Signed in, but without the rights for this.
+    Forbidden,
+    /// The request clashes with existing data, e.g. a taken name.
+    Conflict {
+        message: String,
+    },
+    /// The request body is larger than the server reads.
+    TooLarge,
+    /// Anything the user can do nothing about. Details stay in the server log.
+    Internal,
+}
+
+impl AppError {
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Logs `err` and hides it behind [`AppError::Internal`].
+    pub fn internal(err: impl fmt::Display) -> Self {
+        dioxus::logger::tracing::error!(%err, "internal error");
+        Self::Internal
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Validation { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            Self::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// The message for `field`, if this is a validation error about it.
+    pub fn field_message(&self, field: &str) -> Option<&str> {
+        match self {
+            Self::Validation { field: f, message } if f == field => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Validation { message, .. } | Self::Conflict { message } => f.write_str(message),
+            Self::NotFound => f.write_str("not found"),
+            Self::Unauthorized => f.write_str("sign in first"),
+            Self::Forbidden => f.write_str("not allowed"),
+            Self::TooLarge => f.write_str("too large"),
+            Self::Internal => f.write_str("something went wrong"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// The status the server responds with.
+impl AsStatusCode for AppError {
+    fn as_status_code(&self) -> StatusCode {
+        self.status()
+    }
+}
+
+/// The 0.7 encoding: the status as `code`, the variant as `details`.
+impl From<AppError> for ServerFnError {
+    fn from(err: AppError) -> Self {
+        ServerFnError::ServerError {
+            message: err.to_string(),
+            code: err.status().as_u16(),
+            details: serde_json::to_value(&err).ok(),
+        }
+    }
+}
+
+/// Decodes the encoding above, and maps everything else by status: errors
+/// from functions that return `ServerFnError`, and failed requests.
+impl From<ServerFnError> for AppError {
+    fn from(err: ServerFnError) -> Self {
+        match err {
+            ServerFnError::ServerError {
+                message,
+                code,
+                details,
+            } => {
+                if let Some(err) = details.and_then(|details| serde_json::from_value(details).ok())
+                {
+                    return err;
+                }
+                match code {
+                    401 => Self::Unauthorized,
+                    403 => Self::Forbidden,
+                    404 => Self::NotFound,
+                    409 => Self::Conflict { message },
+                    413 => Self::TooLarge,
+                    422 => Self::validation(FORM_FIELD, message),
+                    _ => Self::internal(message),
+                }
+            }
+            other => Self::internal(other),
+        }
+    }
+}
+
+/// `use_action` hands errors over as `CapturedError`; get the `AppError` back.
+impl From<CapturedError> for AppError {
+    fn from(err: CapturedError) -> Self {
+        match err.downcast_ref::<AppError>() {
+            Some(err) => err.clone(),
+            None => Self::internal(err),
+        }
+    }
+}
+
+/// Lets a server function return `input.validate()?` as an [`AppError`],
+/// reporting the first field that failed.
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        match first_messages(&errors).into_iter().next() {
+            Some((field, message)) => AppError::validation(field, message),
+            None => AppError::validation(FORM_FIELD, "invalid input"),
+        }
+    }
+}
+
+/// The first message per field, ordered by field name. Errors from a
+/// `#[validate(schema(...))]` rule, which validator files under `__all__`,
+/// are the form's: they come under [`FORM_FIELD`].
+pub fn first_messages(errors: &ValidationErrors) -> BTreeMap<String, String> {
+    errors
+        .field_errors()
+        .into_iter()
+        .filter_map(|(field, errors)| {
+            let error = errors.first()?;
+            let message = match &error.message {
+                Some(message) => message.to_string(),
+                None => error.code.to_string(),
+            };
+            let field = match &*field {
+                "__all__" => FORM_FIELD,
+                field => field,
+            };
+            Some((field.to_string(), message))
+        })
+        .collect()
+}
+
+/// The validation message for `field` in `error`, if any. Put it next to the
+/// field's input.
+#[component]
+pub fn FieldError(error: Option<AppError>, field: String) -> Element {
+    match error.as_ref().and_then(|err| err.field_message(&field)) {
+        Some(message) => rsx! { p { class: "field-error", "{message}" } },
+        None => rsx! {},
+    }
+}
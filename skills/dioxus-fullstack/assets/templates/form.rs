@@ -0,0 +1,465 @@
+// Dioxus 0.7 Form Template
+//
+// Forms over a struct that derives `serde` and `validator::Validate`:
+// `use_form::<T>()` keeps the field values, runs `T::validate()` whenever they
+// change, and tracks which fields were touched (left once) or changed. Input
+// components bind to a field by name: text inputs to `String` fields,
+// `Checkbox` to `bool` fields. The server function validates the same struct
+// again, since anyone can call it over HTTP.
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+
+use dioxus::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use validator::Validate;
+
+use crate::error::{first_messages, AppError, FORM_FIELD};
+
+/// What `use_form` needs from the struct behind a form.
+pub trait FormData: Validate + Serialize + DeserializeOwned + Default + 'static {}
+
+impl<T: Validate + Serialize + DeserializeOwned + Default + 'static> FormData for T {}
+
+/// Form state starting from `T::default()`.
+pub fn use_form<T: FormData>() -> Form<T> {
+    let values = use_signal(|| to_map(&T::default()));
+    let initial = use_hook(|| CopyValue::new(to_map(&T::default())));
+    let errors = use_memo(move || validate::<T>(&values.read()));
+
+    Form {
+        state: FormState {
+            values,
+            initial,
+            errors,
+            touched: use_signal(BTreeSet::new),
+            server_errors: use_signal(BTreeMap::new),
+            submitted: use_signal(|| false),
+        },
+        _data: PhantomData,
+    }
+}
+
+/// A form over `T`. Cheap to copy into event handlers.
+pub struct Form<T: 'static> {
+    state: FormState,
+    _data: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Form<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Form<T> {}
+
+/// Everything but the type, so fields and components need no type parameter.
+#[derive(Clone, Copy, PartialEq)]
+struct FormState {
+    values: Signal<Map<String, Value>>,
+    initial: CopyValue<Map<String, Value>>,
+    /// Every field's first validation message, shown or not.
+    errors: Memo<BTreeMap<String, String>>,
+    touched: Signal<BTreeSet<String>>,
+    /// Errors the server reported, until the field changes.
+    server_errors: Signal<BTreeMap<String, String>>,
+    submitted: Signal<bool>,
+}
+
+impl<T: FormData> Form<T> {
+    /// The field named `name` in `T`, to hand to an input component.
+    pub fn field(&self, name: &'static str) -> Field {
+        // A misspelled name would bind to nothing and never show an error.
+        debug_assert!(
+            self.state.initial.read().contains_key(name),
+            "`{name}` is not a field of {}",
+            std::any::type_name::<T>()
+        );
+        Field {
+            state: self.state,
+            name,
+        }
+    }
+
+    /// The current values, if they parse as `T`. Not necessarily valid.
+    pub fn value(&self) -> Option<T> {
+        serde_json::from_value(Value::Object(self.state.values.cloned())).ok()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.state.errors.read().is_empty()
+    }
+
+    /// Whether any field differs from its initial value.
+    pub fn is_dirty(&self) -> bool {
+        *self.state.values.read() != *self.state.initial.read()
+    }
+
+    /// Shows every field's error, and returns the values if they are valid.
+    /// Call it from `onsubmit` and send what it returns to the server.
+    pub fn submit(&self) -> Option<T> {
+        let mut submitted = self.state.submitted;
+        submitted.set(true);
+        if self.is_valid() {
+            self.value()
+        } else {
+            None
+        }
+    }
+
+    /// A field standing for the form as a whole, for [`FormError`]: it holds
+    /// errors no input is to blame for, e.g. a value of the wrong kind.
+    pub fn form_error(&self) -> Field {
+        Field {
+            state: self.state,
+            name: FORM_FIELD,
+        }
+    }
+
+    /// Shows a validation error from the server function next to its field,
+    /// or in [`FormError`] when it is about the whole form. Other errors are
+    /// left to the caller.
+    pub fn set_server_error(&self, err: &AppError) {
+        if let AppError::Validation { field, message } = err {
+            let mut server_errors = self.state.server_errors;
+            server_errors.write().insert(field.clone(), message.clone());
+        }
+    }
+
+    /// Back to `T::default()`, untouched.
+    pub fn reset(&self) {
+        let FormState {
+            mut values,
+            initial,
+            mut touched,
+            mut server_errors,
+            mut submitted,
+            ..
+        } = self.state;
+        values.set(initial.cloned());
+        touched.write().clear();
+        server_errors.write().clear();
+        submitted.set(false);
+    }
+}
+
+/// One field of a [`Form`], by name.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Field {
+    state: FormState,
+    name: &'static str,
+}
+
+impl Field {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The value as text: strings as they are, `null` as empty.
+    pub fn text(&self) -> String {
+        match self.state.values.read().get(self.name) {
+            Some(Value::String(text)) => text.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        }
+    }
+
+    pub fn checked(&self) -> bool {
+        matches!(
+            self.state.values.read().get(self.name),
+            Some(Value::Bool(true))
+        )
+    }
+
+    pub fn set_text(&self, text: String) {
+        self.set(Value::String(text));
+    }
+
+    pub fn set_checked(&self, checked: bool) {
+        self.set(Value::Bool(checked));
+    }
+
+    fn set(&self, value: Value) {
+        let FormState {
+            mut values,
+            mut server_errors,
+            ..
+        } = self.state;
+        values.write().insert(self.name.to_string(), value);
+        // The server judged the old values; drop its verdict on this field
+        // and on the form as a whole.
+        for name in [self.name, FORM_FIELD] {
+            if server_errors.peek().contains_key(name) {
+                server_errors.write().remove(name);
+            }
+        }
+    }
+
+    /// Marks the field as visited, so its error shows. Inputs call it on blur.
+    pub fn touch(&self) {
+        let mut touched = self.state.touched;
+        if !touched.peek().contains(self.name) {
+            touched.write().insert(self.name.to_string());
+        }
+    }
+
+    pub fn is_touched(&self) -> bool {
+        self.state.touched.read().contains(self.name)
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.state.values.read().get(self.name) != self.state.initial.read().get(self.name)
+    }
+
+    /// The error to show: the server's, or the first validation message once
+    /// the field was touched or the form submitted.
+    pub fn error(&self) -> Option<String> {
+        if let Some(message) = self.state.server_errors.read().get(self.name) {
+            return Some(message.clone());
+        }
+        if !self.is_touched() && !(self.state.submitted)() {
+            return None;
+        }
+        self.state.errors.read().get(self.name).cloned()
+    }
+}
+
+fn to_map<T: Serialize>(data: &T) -> Map<String, Value> {
+    match serde_json::to_value(data) {
+        Ok(Value::Object(map)) => map,
+        _ => panic!("form data must serialize to a struct"),
+    }
+}
+
+fn validate<T: FormData>(values: &Map<String, Value>) -> BTreeMap<String, String> {
+    match serde_json::from_value::<T>(Value::Object(values.clone())) {
+        Ok(data) => match data.validate() {
+            Ok(()) => BTreeMap::new(),
+            Err(errors) => first_messages(&errors),
+        },
+        // A field holds the wrong kind of value, e.g. text bound to a number:
+        // no input is to blame, so it is the whole form's error.
+        Err(err) => BTreeMap::from([(FORM_FIELD.to_string(), err.to_string())]),
+    }
+}
+
+/// Label, input and error message, the markup every input component shares.
+#[component]
+fn FieldShell(field: Field, label: String, children: Element) -> Element {
+    let error = field.error();
+    let class = if error.is_some() {
+        "field invalid"
+    } else {
+        "field"
+    };
+
+    rsx! {
+        div { class,
+            label { r#for: field.name(), "{label}" }
+            {children}
+            if let Some(error) = error {
+                p { class: "field-error", "{error}" }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn TextInput(
+    field: Field,
+    label: String,
+    #[props(default)] placeholder: String,
+    #[props(default = "text".to_string())] r#type: String,
+) -> Element {
+    rsx! {
+        FieldShell { field, label,
+            input {
+                id: field.name(),
+                name: field.name(),
+                r#type,
+                placeholder,
+                value: field.text(),
+                aria_invalid: field.error().is_some(),
+                oninput: move |e| field.set_text(e.value()),
+                onblur: move |_| field.touch(),
+            }
+        }
+    }
+}
+
+#[component]
+pub fn PasswordInput(field: Field, label: String) -> Element {
+    rsx! {
+        TextInput { field, label, r#type: "password" }
+    }
+}
+
+#[component]
+pub fn TextArea(field: Field, label: String, #[props(default = 4)] rows: u32) -> Element {
+    rsx! {
+        FieldShell { field, label,
+            textarea {
+                id: field.name(),
+                name: field.name(),
+                rows,
+                value: field.text(),
+                aria_invalid: field.error().is_some(),
+                oninput: move |e| field.set_text(e.value()),
+                onblur: move |_| field.touch(),
+            }
+        }
+    }
+}
+
+/// A drop-down over `(value, label)` pairs, starting with an empty choice.
+#[component]
+pub fn Select(field: Field, label: String, options: Vec<(&'static str, &'static str)>) -> Element {
+    let selected = field.text();
+
+    rsx! {
+        FieldShell { field, label,
+            select {
+                id: field.name(),
+                name: field.name(),
+                aria_invalid: field.error().is_some(),
+                onchange: move |e| field.set_text(e.value()),
+                onblur: move |_| field.touch(),
+                option { value: "", selected: selected.is_empty(), "Choose..." }
+                for (value, text) in options {
+                    option { key: "{value}", value, selected: selected == value, "{text}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn Checkbox(field: Field, label: String) -> Element {
+    let error = field.error();
+    let class = if error.is_some() {
+        "field invalid"
+    } else {
+        "field"
+    };
+
+    rsx! {
+        div { class,
+            label {
+                input {
+                    name: field.name(),
+                    r#type: "checkbox",
+                    checked: field.checked(),
+                    aria_invalid: class != "field",
+                    onchange: move |e| {
+                        field.set_checked(e.checked());
+                        field.touch();
+                    },
+                }
+                "{label}"
+            }
+            if let Some(error) = error {
+                p { class: "field-error", "{error}" }
+            }
+        }
+    }
+}
+
+/// The error about the form as a whole, once it was submitted. Put it above
+/// the submit button: `FormError { field: form.form_error() }`.
+#[component]
+pub fn FormError(field: Field) -> Element {
+    match field.error() {
+        Some(error) => rsx! { p { class: "form-error", role: "alert", "{error}" } },
+        None => rsx! {},
+    }
+}
+
+/// Example form data: each rule runs on the client as the user types, and
+/// again in `sign_up` on the server. The `schema` rule checks fields against
+/// each other, once each passes its own rules; `FormError` shows its message.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "password_is_not_email"))]
+pub struct SignUp {
+    #[validate(email(message = "Enter a valid email address"))]
+    pub email: String,
+    #[validate(length(min = 8, message = "Use at least 8 characters"))]
+    pub password: String,
+    #[validate(length(min = 1, message = "Choose a plan"))]
+    pub plan: String,
+    #[validate(length(max = 500, message = "Keep it under 500 characters"))]
+    pub bio: String,
+    #[validate(custom(function = "accepted", message = "Accept the terms to continue"))]
+    pub accept_terms: bool,
+}
+
+fn accepted(value: &bool) -> Result<(), validator::ValidationError> {
+    if *value {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("accepted"))
+    }
+}
+
+fn password_is_not_email(input: &SignUp) -> Result<(), validator::ValidationError> {
+    if input.password == input.email {
+        let error = validator::ValidationError::new("password_is_email");
+        Err(error.with_message("Use a password other than your email".into()))
+    } else {
+        Ok(())
+    }
+}
+
+#[server]
+pub async fn sign_up(input: SignUp) -> Result<String, AppError> {
+    input.validate()?;
+
+    // Create the account here; `auth.rs` has registration with hashing.
+    Ok(format!("Welcome, {}!", input.email))
+}
+
+#[component]
+pub fn SignUpForm() -> Element {
+    let form = use_form::<SignUp>();
+    let mut sign_up = use_action(sign_up);
+
+    // Server-side validation errors go next to their fields too.
+    use_effect(move || {
+        if let Some(Err(err)) = sign_up.value() {
+            form.set_server_error(&AppError::from(err));
+        }
+    });
+
+    rsx! {
+        form {
+            class: "sign-up",
+            novalidate: true,
+            onsubmit: move |e: FormEvent| {
+                e.prevent_default();
+                if let Some(input) = form.submit() {
+                    sign_up.call(input);
+                }
+            },
+            TextInput { field: form.field("email"), label: "Email", r#type: "email" }
+            PasswordInput { field: form.field("password"), label: "Password" }
+            Select {
+                field: form.field("plan"),
+                label: "Plan",
+                options: vec![("free", "Free"), ("pro", "Pro")],
+            }
+            TextArea { field: form.field("bio"), label: "About you" }
+            Checkbox { field: form.field("accept_terms"), label: "I accept the terms" }
+            FormError { field: form.form_error() }
+            button { r#type: "submit", disabled: sign_up.pending(), "Sign up" }
+            match sign_up.value() {
+                Some(Ok(message)) => rsx! { p { class: "success", "{message}" } },
+                Some(Err(err)) => match AppError::from(err) {
+                    AppError::Validation { .. } => rsx! {},
+                    err => rsx! { p { class: "error", "{err}" } },
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+// Dioxus 0.7 Hooks Template
+//
+// Custom hooks are plain functions whose names start with `use_`; there is no
+// attribute for them. Like the built-in hooks they must be called on every
+// render, in the same order, and never inside conditions or loops. Tasks a
+// hook spawns belong to the component that called it and stop when it
+// unmounts.
+use std::future::Future;
+use std::time::Duration;
+
+use dioxus::core::Task;
+use dioxus::prelude::*;
+
+use crate::error::AppError;
+
+/// `value`, once it has stopped changing for `delay`. Every change cancels
+/// the wait started by the one before, so only the last value comes through.
+pub fn use_debounce<T: Clone + PartialEq + 'static>(
+    value: ReadSignal<T>,
+    delay: Duration,
+) -> ReadSignal<T> {
+    let mut debounced = use_signal(|| value.cloned());
+    let mut pending = use_hook(|| CopyValue::new(None::<Task>));
+
+    use_effect(move || {
+        let value = value();
+        if let Some(task) = pending.take() {
+            task.cancel();
+        }
+        // Changing back before the delay is up cancels the change.
+        if *debounced.peek() == value {
+            return;
+        }
+        pending.set(Some(spawn(async move {
+            futures_timer::Delay::new(delay).await;
+            debounced.set(value);
+        })));
+    });
+
+    debounced.into()
+}
+
+/// The state of a request made with [`use_api`].
+pub struct Api<T: 'static> {
+    resource: Resource<Result<T, AppError>>,
+}
+
+impl<T> Clone for Api<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Api<T> {}
+
+impl<T: Clone> Api<T> {
+    /// The last successful result. It stays while a refetch is running.
+    pub fn data(&self) -> Option<T> {
+        match &*self.resource.read() {
+            Some(Ok(data)) => Some(data.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<AppError> {
+        match &*self.resource.read() {
+            Some(Err(err)) => Some(err.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn loading(&self) -> bool {
+        self.resource.pending()
+    }
+
+    /// Runs the request again, e.g. after a change the server made.
+    pub fn refetch(&self) {
+        let mut resource = self.resource;
+        resource.restart();
+    }
+}
+
+/// Runs `fetch`, usually a server function call, and again whenever a signal
+/// it reads changes or [`Api::refetch`] is called. Errors become `AppError`s.
+pub fn use_api<T, E, F>(mut fetch: impl FnMut() -> F + 'static) -> Api<T>
+where
+    T: 'static,
+    E: Into<AppError>,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let resource = use_resource(move || {
+        let request = fetch();
+        async move { request.await.map_err(Into::into) }
+    });
+    Api { resource }
+}
+
+/// A timer started by [`use_timeout`] or [`use_interval`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct Timer {
+    task: Signal<Option<Task>>,
+    start: Callback<(), Task>,
+}
+
+impl Timer {
+    /// Starts the timer from the beginning, whether it is running or not.
+    pub fn restart(&self) {
+        self.cancel();
+        let mut task = self.task;
+        task.set(Some(self.start.call(())));
+    }
+
+    pub fn cancel(&self) {
+        let mut task = self.task;
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+    }
+
+    /// Whether the timer is counting down. Reading it in a component
+    /// re-renders the component when that changes.
+    pub fn is_running(&self) -> bool {
+        self.task.read().is_some()
+    }
+}
+
+/// Calls `callback` once, `delay` after the component mounts. Restart the
+/// timer to wait again, e.g. to hide a notification a while after the
+/// latest one.
+pub fn use_timeout(delay: Duration, mut callback: impl FnMut() + 'static) -> Timer {
+    let callback = use_callback(move |()| callback());
+    let mut task = use_signal(|| None);
+    let start = use_callback(move |()| {
+        spawn(async move {
+            futures_timer::Delay::new(delay).await;
+            task.set(None);
+            callback.call(());
+        })
+    });
+
+    let timer = Timer { task, start };
+    use_hook(|| timer.restart());
+    timer
+}
+
+/// Calls `callback` every `period` from when the component mounts. Cancel
+/// the timer to pause it and restart it to carry on.
+pub fn use_interval(period: Duration, mut callback: impl FnMut() + 'static) -> Timer {
+    let callback = use_callback(move |()| callback());
+    let task = use_signal(|| None);
+    let start = use_callback(move |()| {
+        spawn(async move {
+            loop {
+                futures_timer::Delay::new(period).await;
+                callback.call(());
+            }
+        })
+    });
+
+    let timer = Timer { task, start };
+    use_hook(|| timer.restart());
+    timer
+}
+
+/// The value `value` had before its latest change, or `None` if it has not
+/// changed yet. Setting it to the same value again is not a change.
+pub fn use_previous<T: Clone + PartialEq + 'static>(value: ReadSignal<T>) -> ReadSignal<Option<T>> {
+    let mut last = use_hook(|| CopyValue::new(value.cloned()));
+    let mut previous = use_hook(|| CopyValue::new(None));
+
+    let memo = use_memo(move || {
+        let value = value();
+        let old = std::mem::replace(&mut *last.write(), value.clone());
+        if old != value {
+            previous.set(Some(old));
+        }
+        previous.cloned()
+    });
+    memo.into()
+}
+
+/// An on/off state, e.g. whether a menu is open.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Toggle {
+    on: Signal<bool>,
+}
+
+impl Toggle {
+    pub fn is_on(&self) -> bool {
+        (self.on)()
+    }
+
+    pub fn toggle(&self) {
+        let mut on = self.on;
+        let flipped = !*on.peek();
+        on.set(flipped);
+    }
+
+    pub fn set(&self, value: bool) {
+        let mut on = self.on;
+        on.set(value);
+    }
+}
+
+pub fn use_toggle(initial: bool) -> Toggle {
+    Toggle {
+        on: use_signal(|| initial),
+    }
+}
@@ -0,0 +1,172 @@
+// Dioxus 0.7 Entrypoint Template
+//
+// `dx serve` builds this file twice for a fullstack app: once for the client
+// with the platform feature (`web`, `desktop` or `mobile`) and once for the
+// server with `server`. Each build takes one branch of `main`.
+//
+// The other templates are this crate's modules, each copied to `src/` under
+// its own name. `server.rs` only builds for the server.
+mod admin;
+mod auth;
+mod boundary;
+mod chat;
+mod component;
+mod crud;
+mod error;
+mod form;
+mod hooks;
+mod route;
+#[cfg(feature = "server")]
+mod server;
+mod server_function;
+mod ssg;
+mod store;
+mod stream;
+mod streaming;
+mod upload;
+
+use dioxus::logger::tracing::Level;
+
+#[cfg(not(feature = "server"))]
+use crate::route::App;
+
+fn main() {
+    let config = Config::load();
+    dioxus::logger::init(config.log_level).expect("the logger is only initialized once");
+
+    // `app prerender [BLOG_ID...]` writes the static pages to `DIST_DIR` and
+    // exits instead of serving; see `ssg.rs`. It is a subcommand rather than
+    // a binary of its own because this crate is a single binary: another one
+    // could not reach the app's modules without a library target.
+    #[cfg(feature = "server")]
+    if std::env::args().nth(1).as_deref() == Some("prerender") {
+        let blog_ids = match blog_ids(std::env::args().skip(2)) {
+            Ok(ids) => ids,
+            Err(err) => {
+                eprintln!("{err}\nusage: app prerender [BLOG_ID...]");
+                std::process::exit(2);
+            }
+        };
+        if let Err(err) = prerender(config, blog_ids) {
+            eprintln!("prerendering failed: {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Server: Dioxus renders `App` and registers every `#[server]` function on
+    // our own axum router, so plain HTTP routes can live next to the app.
+    #[cfg(feature = "server")]
+    dioxus::serve(move || {
+        let config = config.clone();
+        async move { Ok(router(config).await?) }
+    });
+
+    // Client (web, desktop or mobile): launch the app for the enabled
+    // platform. Server functions are called over HTTP from here.
+    #[cfg(not(feature = "server"))]
+    dioxus::launch(App);
+}
+
+/// The blog post ids given to `prerender`, or which argument is not one.
+#[cfg(feature = "server")]
+fn blog_ids(args: impl Iterator<Item = String>) -> Result<Vec<u32>, String> {
+    args.map(|arg| {
+        arg.parse()
+            .map_err(|_| format!("`{arg}` is not a blog post id"))
+    })
+    .collect()
+}
+
+/// Settings read once at startup.
+#[derive(Debug, Clone)]
+struct Config {
+    /// `LOG_LEVEL`: `error`, `warn`, `info`, `debug` or `trace`.
+    log_level: Level,
+    /// `DATABASE_URL`, only needed where the database is: on the server.
+    #[cfg(feature = "server")]
+    database_url: String,
+    /// `UPLOAD_DIR`, where uploaded files are stored.
+    #[cfg(feature = "server")]
+    upload_dir: std::path::PathBuf,
+    /// `DIST_DIR`, where prerendered pages are written and served from.
+    #[cfg(feature = "server")]
+    dist_dir: std::path::PathBuf,
+}
+
+impl Config {
+    fn load() -> Self {
+        let default_level = if cfg!(debug_assertions) {
+            Level::DEBUG
+        } else {
+            Level::INFO
+        };
+
+        Self {
+            log_level: var("LOG_LEVEL")
+                .and_then(|level| level.parse().ok())
+                .unwrap_or(default_level),
+            #[cfg(feature = "server")]
+            database_url: var("DATABASE_URL").unwrap_or_else(|| "sqlite://app.db".to_string()),
+            #[cfg(feature = "server")]
+            upload_dir: var("UPLOAD_DIR")
+                .unwrap_or_else(|| "uploads".to_string())
+                .into(),
+            #[cfg(feature = "server")]
+            dist_dir: var("DIST_DIR")
+                .unwrap_or_else(|| crate::ssg::DIST_DIR.to_string())
+                .into(),
+        }
+    }
+}
+
+/// Reads a setting from the environment. Browsers have none, so web builds
+/// read it when they are compiled instead: `LOG_LEVEL=warn dx build`.
+fn var(name: &str) -> Option<String> {
+    if cfg!(target_arch = "wasm32") {
+        // `option_env!` needs a literal, so list the settings the client reads.
+        match name {
+            "LOG_LEVEL" => option_env!("LOG_LEVEL").map(str::to_string),
+            _ => None,
+        }
+    } else {
+        std::env::var(name).ok()
+    }
+}
+
+/// The app from `server.rs`, on the database (opened and migrated once here)
+/// and directories from `config`. Handlers and server functions can take
+/// `Extension<Config>` to read the settings.
+#[cfg(feature = "server")]
+async fn router(config: Config) -> Result<dioxus::server::axum::Router, sqlx::Error> {
+    use dioxus::server::axum::Extension;
+
+    dioxus::logger::tracing::info!(database = %config.database_url, "starting server");
+    let pool = crate::crud::db::connect(&config.database_url).await?;
+    let upload_dir = config.upload_dir.clone();
+    let dist = config.dist_dir.clone();
+    Ok(crate::server::router(pool, upload_dir, dist).layer(Extension(config)))
+}
+
+/// Renders the pages `ssg::routes` lists into `DIST_DIR`, whole, without
+/// streaming, and from the app itself rather than older files.
+#[cfg(feature = "server")]
+fn prerender(config: Config, blog_ids: Vec<u32>) -> Result<(), Box<dyn std::error::Error>> {
+    use dioxus::server::axum::Extension;
+    use dioxus::server::ServeConfig;
+
+    use crate::ssg;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async move {
+        let pool = crate::crud::db::connect(&config.database_url).await?;
+        let dist = config.dist_dir.clone();
+        let app = crate::server::app(ServeConfig::new(), pool, config.upload_dir.clone())
+            .layer(Extension(config));
+        let files = ssg::server::prerender(app, &dist, &ssg::routes(&blog_ids)).await?;
+        for file in files {
+            dioxus::logger::tracing::info!(file = %file.display(), "prerendered");
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,7 @@
+-- Applied in order by `sqlx::migrate!()`. Never edit a migration that has
+-- run somewhere; add a new one.
+CREATE TABLE posts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL UNIQUE CHECK (length(trim(title)) > 0),
+    body TEXT NOT NULL DEFAULT ''
+);
@@ -1,22 +1,65 @@
 // Dioxus 0.7 Route Template
 use dioxus::prelude::*;
-use dioxus_router::prelude::*;
 
+use crate::admin::{AdminDashboard, AdminGuard};
+use crate::auth::{use_current_user, use_current_user_provider, LoginForm, UserMenu};
+use crate::boundary::{AppErrorBoundary, PostPage};
+use crate::streaming::Dashboard;
+
+// Indentation mirrors the nesting: everything between `#[layout]` and
+// `#[end_layout]` renders inside `NavBar`'s `Outlet`. The admin routes sit
+// inside a second layout, `AdminGuard`, which only renders them for admins.
 #[derive(Clone, Routable, Debug, PartialEq)]
+#[rustfmt::skip]
 pub enum Route {
-    #[route("/")]
-    Home {},
-    #[route("/about")]
-    About {},
-    #[route("/blog/:id")]
-    BlogPost { id: u32 },
+    #[layout(NavBar)]
+        #[route("/")]
+        Home {},
+        #[route("/about")]
+        About {},
+        #[route("/blog/:id")]
+        BlogPost { id: u32 },
+        #[route("/posts/:id")]
+        PostPage { id: i64 },
+        #[route("/dashboard")]
+        Dashboard {},
+        #[route("/login?:redirect")]
+        Login { redirect: String },
+        #[nest("/admin")]
+            #[layout(AdminGuard)]
+                #[route("/dashboard")]
+                AdminDashboard {},
+            #[end_layout]
+        #[end_nest]
+    #[end_layout]
     #[route("/:..route")]
     NotFound { route: Vec<String> },
 }
 
+/// Layout shared by every route inside `#[layout(NavBar)]`.
+#[component]
+fn NavBar() -> Element {
+    let route = use_route::<Route>();
+
+    rsx! {
+        nav { class: "navbar",
+            Link { to: Route::Home {}, active_class: "active", "Home" }
+            Link { to: Route::About {}, active_class: "active", "About" }
+            Link { to: Route::AdminDashboard {}, active_class: "active", "Admin" }
+            UserMenu {}
+        }
+        // A page that fails shows the error in place of the page, under the
+        // navbar. The key starts a new boundary, without the error, on every
+        // navigation.
+        main {
+            AppErrorBoundary { key: "{route}", Outlet::<Route> {} }
+        }
+    }
+}
+
 #[component]
-fn Home(cx: Scope) -> Element {
-    render! {
+fn Home() -> Element {
+    rsx! {
         div { class: "home",
             h1 { "Welcome to Dioxus 0.7" }
             Link { to: Route::About {}, "About" }
@@ -26,8 +69,8 @@ fn Home(cx: Scope) -> Element {
 }
 
 #[component]
-fn About(cx: Scope) -> Element {
-    render! {
+fn About() -> Element {
+    rsx! {
         div { class: "about",
             h1 { "About" }
             Link { to: Route::Home {}, "Back to Home" }
@@ -36,30 +79,65 @@ fn About(cx: Scope) -> Element {
 }
 
 #[component]
-fn BlogPost(cx: Scope, id: u32) -> Element {
-    render! {
+fn BlogPost(id: u32) -> Element {
+    let navigator = use_navigator();
+
+    rsx! {
         div { class: "blog-post",
             h1 { "Blog Post {id}" }
             p { "This is blog post number {id}" }
+            if id > 1 {
+                button { onclick: move |_| { navigator.push(Route::BlogPost { id: id - 1 }); }, "Previous" }
+            }
+            button { onclick: move |_| { navigator.push(Route::BlogPost { id: id + 1 }); }, "Next" }
             Link { to: Route::Home {}, "Back to Home" }
         }
     }
 }
 
+/// Sign-in page. `redirect` is the path to return to afterwards, set by
+/// `AdminGuard` to the page it turned the visitor away from.
+#[component]
+fn Login(redirect: String) -> Element {
+    let current = use_current_user();
+    let navigator = use_navigator();
+
+    use_effect(move || {
+        if current.user().is_some() {
+            // Only paths of this app: a crafted `?redirect=` cannot send the
+            // user to another site.
+            navigator.replace(redirect.parse::<Route>().unwrap_or(Route::Home {}));
+        }
+    });
+
+    rsx! {
+        div { class: "login",
+            h1 { "Sign in" }
+            LoginForm {}
+        }
+    }
+}
+
 #[component]
-fn NotFound(cx: Scope, route: Vec<String>) -> Element {
-    render! {
+fn NotFound(route: Vec<String>) -> Element {
+    let navigator = use_navigator();
+
+    rsx! {
         div { class: "not-found",
             h1 { "404 - Not Found" }
-            p { "Route: {route.join(\"/\")}" }
+            p { "Route: /{route.join(\"/\")}" }
+            button { onclick: move |_| navigator.go_back(), "Go back" }
             Link { to: Route::Home {}, "Home" }
         }
     }
 }
 
 #[component]
-pub fn App(cx: Scope) -> Element {
-    render! {
+pub fn App() -> Element {
+    // Every page can read the signed-in user with `use_current_user()`.
+    use_current_user_provider();
+
+    rsx! {
         Router::<Route> {}
     }
-}
\ No newline at end of file
+}
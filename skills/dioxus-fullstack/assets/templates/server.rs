@@ -0,0 +1,46 @@
+// Dioxus 0.7 Server Template
+//
+// The app as the server serves it: pages, server functions and our own
+// routes, with the layers they rely on. Only the server build has it, so
+// declare it with `#[cfg(feature = "server")] mod server;`. `main.rs` serves
+// `router` on the database and directories from its settings, and so do the
+// integration tests on temporary ones.
+use std::path::PathBuf;
+
+use dioxus::server::axum::routing::get;
+use dioxus::server::axum::{middleware, Extension, Router};
+use dioxus::server::{DioxusRouterExt, ServeConfig};
+
+use crate::auth::server::session_layer;
+use crate::chat::server as chat;
+use crate::crud::db::Db;
+use crate::route::App;
+use crate::ssg::{self, server::Prerendered};
+use crate::upload::server::UploadDir;
+
+/// The app as it is served. Pages stream: each `SuspenseBoundary` is sent
+/// when its data is ready, see `streaming.rs`. Prerendered pages in `dist`
+/// are answered from their files while those are fresh, see `ssg.rs`.
+pub fn router(pool: Db, upload_dir: PathBuf, dist: PathBuf) -> Router {
+    // Stale pages are rendered again the way `prerender` renders them:
+    // whole, without streaming.
+    let whole = app(ServeConfig::new(), pool.clone(), upload_dir.clone());
+    let pages = Prerendered::new(dist, ssg::TTL, whole);
+    let serve = ServeConfig::new().enable_out_of_order_streaming();
+    app(serve, pool, upload_dir).layer(middleware::from_fn_with_state(pages, ssg::server::serve))
+}
+
+/// The app and its server functions, rendering pages with `serve`, plus our
+/// own routes. Server functions can take `Extension<Db>` for the database
+/// pool. The session layer gives them the signed-in user's cookie session,
+/// and the chat socket shares one `Hub`.
+pub fn app(serve: ServeConfig, pool: Db, upload_dir: PathBuf) -> Router {
+    Router::new()
+        .serve_dioxus_application(serve, App)
+        .route("/health", get(|| async { "ok" }))
+        .merge(chat::routes())
+        .layer(Extension(chat::Hub::default()))
+        .layer(Extension(UploadDir(upload_dir)))
+        .layer(session_layer())
+        .layer(Extension(pool))
+}
@@ -1,15 +1,33 @@
 // Dioxus 0.7 Server Function Template
+use std::time::Duration;
+
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::error::{AppError, FieldError};
+use crate::hooks::use_debounce;
+
+/// How long the input has to stay unchanged before it is sent to the server.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResponseData {
     pub id: u32,
     pub message: String,
 }
 
 #[server]
-pub async fn server_function(param: String) -> Result<ResponseData, ServerFnError> {
+pub async fn server_function(param: String) -> Result<ResponseData, AppError> {
+    // Check input on the server even if the client does too: anyone can call
+    // this over HTTP.
+    const MAX_LEN: usize = 100;
+    if param.chars().count() > MAX_LEN {
+        return Err(AppError::validation(
+            "query",
+            format!("Keep it under {MAX_LEN} characters"),
+        ));
+    }
+
     // Server-side logic here
     // Database queries, API calls, etc.
 
@@ -22,20 +40,12 @@ pub async fn server_function(param: String) -> Result<ResponseData, ServerFnErro
 }
 
 #[component]
-pub fn ServerComponent(cx: Scope) -> Element {
-    let mut input = use_signal(cx, || "".to_string());
-    let result = use_resource(cx, || async move {
-        if !input.read().is_empty() {
-            server_function(input.read().clone()).await
-        } else {
-            Ok(ResponseData {
-                id: 0,
-                message: "Enter input".to_string(),
-            })
-        }
-    });
+pub fn ServerComponent() -> Element {
+    let mut input = use_signal(String::new);
+    // Only sent once typing pauses, instead of on every key.
+    let query = use_debounce(input.into(), DEBOUNCE);
 
-    render! {
+    rsx! {
         div { class: "server-component",
             input {
                 value: "{input}",
@@ -43,16 +53,53 @@ pub fn ServerComponent(cx: Scope) -> Element {
                 placeholder: "Enter data"
             }
 
-            div {
-                match result.read().as_ref() {
-                    Some(Ok(data)) => rsx! {
-                        p { "ID: {data.id}" }
-                        p { "Message: {data.message}" }
-                    },
-                    Some(Err(e)) => rsx! { p { "Error: {e}" } },
-                    None => rsx! { p { "Loading..." } },
-                }
+            // The result suspends while the server responds; the boundary keeps
+            // the input above mounted (and focused) in the meantime.
+            SuspenseBoundary {
+                fallback: |_| rsx! { p { "Loading..." } },
+                ServerResult { query }
+            }
+        }
+    }
+}
+
+#[component]
+fn ServerResult(query: ReadSignal<String>) -> Element {
+    // The closure reads `query`, so the future re-runs whenever the debounced
+    // value changes. `?` suspends until the result is ready: on the server the
+    // first result is rendered into the HTML and serialized with it, and the
+    // client hydrates from that instead of calling the server again.
+    let result = use_server_future(move || {
+        let query = query();
+        async move {
+            if query.is_empty() {
+                Ok(ResponseData {
+                    id: 0,
+                    message: "Enter input".to_string(),
+                })
+            } else {
+                server_function(query).await
+            }
+        }
+    })?;
+
+    // A resource holds `None` until its first run finishes and keeps the last
+    // value while a re-run is pending, so `None` only shows up if this
+    // component stops suspending (e.g. after switching to `use_resource`).
+    rsx! {
+        div { class: "result",
+            match &*result.read() {
+                Some(Ok(data)) => rsx! {
+                    p { "ID: {data.id}" }
+                    p { "Message: {data.message}" }
+                },
+                // Validation errors go next to the input they are about.
+                Some(Err(e @ AppError::Validation { .. })) => rsx! {
+                    FieldError { error: e.clone(), field: "query" }
+                },
+                Some(Err(e)) => rsx! { p { class: "error", "Error: {e}" } },
+                None => rsx! { p { "Loading..." } },
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,232 @@
+// Dioxus 0.7 Static Site Generation Template
+//
+// Pages that look the same for every visitor, like the blog, can be rendered
+// once at build time instead of on every request. Running the server binary
+// with `prerender` writes each of them to `dist/<path>/index.html`, hydration
+// data included, so the client takes over the page as if the server had
+// just rendered it:
+//
+//     cargo run --features server -- prerender 1 2 3    # the blog post ids
+//
+// Run it from the bundled server (`dx bundle --platform web`) so the pages
+// load the client's scripts. When serving, `server::router` answers from those
+// files. Once a file is older than `TTL` it is served one more time while the
+// page is rendered again in the background, so a change shows up without
+// another build and no visitor waits for it.
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use dioxus::prelude::Routable;
+
+use crate::route::Route;
+
+/// Where prerendered pages go, relative to the working directory.
+pub const DIST_DIR: &str = "dist";
+
+/// How long a prerendered page is served before it is rendered again.
+pub const TTL: Duration = Duration::from_secs(10 * 60);
+
+/// What to prerender: every route without parameters, except pages that
+/// differ per visitor or show live data, and the blog post for each id.
+pub fn routes(blog_ids: &[u32]) -> Vec<Route> {
+    Route::static_routes()
+        .into_iter()
+        .filter(|route| {
+            !matches!(
+                route,
+                Route::Login { .. } | Route::AdminDashboard {} | Route::Dashboard {}
+            )
+        })
+        .chain(blog_ids.iter().map(|&id| Route::BlogPost { id }))
+        .collect()
+}
+
+/// The file the page at `path` is written to: `dist/blog/1/index.html` for
+/// `/blog/1`. `None` for paths that could point outside `dist`.
+pub fn page_file(dist: &Path, path: &str) -> Option<PathBuf> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut file = dist.to_path_buf();
+    for part in Path::new(path.trim_start_matches('/')).components() {
+        match part {
+            Component::Normal(part) => file.push(part),
+            _ => return None,
+        }
+    }
+    file.push("index.html");
+    Some(file)
+}
+
+#[cfg(feature = "server")]
+pub mod server {
+    use std::collections::HashSet;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use dioxus::server::axum::body::{to_bytes, Body, Bytes};
+    use dioxus::server::axum::extract::{Request, State};
+    use dioxus::server::axum::http::{header, Method, StatusCode};
+    use dioxus::server::axum::middleware::Next;
+    use dioxus::server::axum::response::{IntoResponse, Response};
+    use dioxus::server::axum::Router;
+    use tower::ServiceExt;
+
+    use super::page_file;
+    use crate::route::Route;
+
+    /// Renders each route with `router` and writes it to its file under
+    /// `dist`. Returns the files.
+    pub async fn prerender(
+        router: Router,
+        dist: &Path,
+        routes: &[Route],
+    ) -> io::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for route in routes {
+            let path = route.to_string();
+            let file = page_file(dist, &path).ok_or_else(|| io::Error::other(path.clone()))?;
+            let html = render(&router, &path).await?;
+            write(&file, html).await?;
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    /// The whole page at `path`, as `router` renders it for a visitor who
+    /// is not signed in. Both `prerender` and `serve` render with this, with
+    /// the app's router without streaming, so a page is the same whichever
+    /// wrote it.
+    pub async fn render(router: &Router, path: &str) -> io::Result<Bytes> {
+        let request = Request::get(path)
+            .body(Body::empty())
+            .map_err(io::Error::other)?;
+        let response = router
+            .clone()
+            .oneshot(request)
+            .await
+            .map_err(io::Error::other)?;
+        if response.status() != StatusCode::OK {
+            let message = format!("{path} answered {}", response.status());
+            return Err(io::Error::other(message));
+        }
+        to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(io::Error::other)
+    }
+
+    /// Where prerendered pages are, how long they stay fresh, and what
+    /// renders them again. State for `serve`.
+    #[derive(Clone)]
+    pub struct Prerendered {
+        dist: PathBuf,
+        ttl: Duration,
+        router: Router,
+        /// Files being rendered again right now.
+        rendering: Arc<Mutex<HashSet<PathBuf>>>,
+    }
+
+    impl Prerendered {
+        /// `router` is the app without streaming, the one `prerender` is
+        /// given.
+        pub fn new(dist: PathBuf, ttl: Duration, router: Router) -> Self {
+            Self {
+                dist,
+                ttl,
+                router,
+                rendering: Arc::default(),
+            }
+        }
+
+        /// Claims `file` for rendering again, unless another request already
+        /// has. The claim ends when the guard drops, also when the render
+        /// fails.
+        fn claim(&self, file: &Path) -> Option<Claim> {
+            let mut rendering = self.rendering.lock().expect("never held across a panic");
+            rendering.insert(file.to_path_buf()).then(|| Claim {
+                rendering: self.rendering.clone(),
+                file: file.to_path_buf(),
+            })
+        }
+    }
+
+    /// Owns its share of the set, so it can move into the render's task.
+    struct Claim {
+        rendering: Arc<Mutex<HashSet<PathBuf>>>,
+        file: PathBuf,
+    }
+
+    impl Drop for Claim {
+        fn drop(&mut self) {
+            let mut rendering = self.rendering.lock().expect("never held across a panic");
+            rendering.remove(&self.file);
+        }
+    }
+
+    /// Middleware that answers with the prerendered page for the path, if
+    /// there is one. Once it is older than the TTL the old page is still
+    /// served, and the page is rendered again (and kept) in the background.
+    /// Everything else goes through to the app. Add it with
+    /// `.layer(middleware::from_fn_with_state(prerendered, serve))`.
+    pub async fn serve(State(pages): State<Prerendered>, request: Request, next: Next) -> Response {
+        let path = request.uri().path();
+        // Server functions are never prerendered: leave the disk alone.
+        let page = request.method() == Method::GET
+            && request.uri().query().is_none()
+            && !path.starts_with("/api/");
+        let Some(file) = page.then(|| page_file(&pages.dist, path)).flatten() else {
+            return next.run(request).await;
+        };
+        // No file: not a prerendered page, render it as usual.
+        let Ok(modified) = tokio::fs::metadata(&file)
+            .await
+            .and_then(|meta| meta.modified())
+        else {
+            return next.run(request).await;
+        };
+        let Ok(html) = tokio::fs::read(&file).await else {
+            return next.run(request).await;
+        };
+
+        // One request starts the render; the others keep getting the old page
+        // until the new one is written.
+        let stale = modified.elapsed().is_ok_and(|age| age >= pages.ttl);
+        if let Some(claim) = stale.then(|| pages.claim(&file)).flatten() {
+            tokio::spawn(render_again(pages.router.clone(), path.to_string(), claim));
+        }
+        html_response(html.into())
+    }
+
+    /// Renders the claimed page and replaces its file. A page that fails
+    /// keeps its old file, to be tried again on the next request.
+    async fn render_again(router: Router, path: String, claim: Claim) {
+        let result = match render(&router, &path).await {
+            Ok(html) => write(&claim.file, html).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            dioxus::logger::tracing::warn!(%err, %path, "could not render the page again");
+        }
+    }
+
+    fn html_response(html: Bytes) -> Response {
+        ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html).into_response()
+    }
+
+    /// Writes `file` in one go: readers see the old page or the new one,
+    /// never half of it. The file system calls block, so they run on a
+    /// blocking thread.
+    async fn write(file: &Path, contents: Bytes) -> io::Result<()> {
+        let file = file.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let dir = file.parent().expect("page files are in a directory");
+            std::fs::create_dir_all(dir)?;
+            let mut partial = tempfile::NamedTempFile::new_in(dir)?;
+            io::Write::write_all(&mut partial, &contents)?;
+            partial.persist(&file).map_err(|err| err.error)?;
+            Ok(())
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+}
@@ -0,0 +1,293 @@
+// Dioxus 0.7 Store Template
+//
+// State shared across the app, in two kinds:
+// - `GlobalSignal`/`GlobalMemo` statics, for small values any component may
+//   read or set, like the theme. They need no provider, and every app (or
+//   test `VirtualDom`) gets its own copy.
+// - `AppState`, provided once at the root with `use_context_provider` and
+//   read below it with `use_app_state()`. Every change goes through
+//   `dispatch(Action)`, so the rules live in one reducer, `State::reduce`.
+//
+// Components read slices of `AppState` through selectors built on
+// `use_memo`. A memo only notifies its readers when its value changes, so a
+// component re-renders when its slice changes, not on every dispatch.
+use dioxus::prelude::*;
+
+/// The theme, for every component without passing it down.
+pub static THEME: GlobalSignal<Theme> = Signal::global(|| Theme::Light);
+
+/// Derived from `THEME`, and only recomputed when it changes.
+pub static DARK_MODE: GlobalMemo<bool> = Memo::global(|| THEME() == Theme::Dark);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub fn toggled(self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Todo {
+    pub id: u32,
+    pub title: String,
+    pub done: bool,
+}
+
+/// Which todos the list shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Filter {
+    #[default]
+    All,
+    Active,
+    Done,
+}
+
+impl Filter {
+    pub fn matches(self, todo: &Todo) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Active => !todo.done,
+            Filter::Done => todo.done,
+        }
+    }
+}
+
+/// Everything behind `AppState`. Only `reduce` changes it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct State {
+    pub todos: Vec<Todo>,
+    pub filter: Filter,
+    next_id: u32,
+}
+
+/// A change to `State`, named after what the user did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Add(String),
+    Toggle(u32),
+    Rename { id: u32, title: String },
+    Remove(u32),
+    ClearDone,
+    SetFilter(Filter),
+}
+
+impl State {
+    /// Applies one action. A plain function of the state, so the rules can be
+    /// tested without rendering anything.
+    pub fn reduce(&mut self, action: Action) {
+        match action {
+            Action::Add(title) => {
+                let Some(title) = clean_title(&title) else {
+                    return;
+                };
+                self.next_id += 1;
+                self.todos.push(Todo {
+                    id: self.next_id,
+                    title: title.to_string(),
+                    done: false,
+                });
+            }
+            Action::Toggle(id) => {
+                if let Some(todo) = self.todo_mut(id) {
+                    todo.done = !todo.done;
+                }
+            }
+            Action::Rename { id, title } => {
+                let Some(title) = clean_title(&title) else {
+                    return;
+                };
+                if let Some(todo) = self.todo_mut(id) {
+                    todo.title = title.to_string();
+                }
+            }
+            Action::Remove(id) => self.todos.retain(|todo| todo.id != id),
+            Action::ClearDone => self.todos.retain(|todo| !todo.done),
+            Action::SetFilter(filter) => self.filter = filter,
+        }
+    }
+
+    fn todo_mut(&mut self, id: u32) -> Option<&mut Todo> {
+        self.todos.iter_mut().find(|todo| todo.id == id)
+    }
+}
+
+/// `title` without surrounding whitespace, or `None` if nothing is left.
+fn clean_title(title: &str) -> Option<&str> {
+    Some(title.trim()).filter(|title| !title.is_empty())
+}
+
+/// A handle on the app's `State`. Cheap to copy into event handlers.
+#[derive(Clone, Copy, PartialEq)]
+pub struct AppState {
+    state: Signal<State>,
+}
+
+impl AppState {
+    pub fn dispatch(&self, action: Action) {
+        let mut state = self.state;
+        state.write().reduce(action);
+    }
+}
+
+/// Provides `AppState` to everything below the calling component. Call it
+/// once, in the root.
+pub fn use_app_state_provider(init: impl FnOnce() -> State) -> AppState {
+    use_context_provider(|| AppState {
+        state: Signal::new(init()),
+    })
+}
+
+/// The `AppState` provided above. Panics if there is none.
+pub fn use_app_state() -> AppState {
+    use_context()
+}
+
+/// The part of the state `select` picks. Reading it subscribes to that part
+/// only: the component re-renders when `select` returns something new.
+pub fn use_selector<T: PartialEq + 'static>(select: impl Fn(&State) -> T + 'static) -> Memo<T> {
+    let app = use_app_state();
+    use_memo(move || select(&app.state.read()))
+}
+
+pub fn use_visible_todos() -> Memo<Vec<Todo>> {
+    use_selector(|state| {
+        state
+            .todos
+            .iter()
+            .filter(|todo| state.filter.matches(todo))
+            .cloned()
+            .collect()
+    })
+}
+
+/// How many todos are not done yet.
+pub fn use_remaining() -> Memo<usize> {
+    use_selector(|state| state.todos.iter().filter(|todo| !todo.done).count())
+}
+
+pub fn use_filter() -> Memo<Filter> {
+    use_selector(|state| state.filter)
+}
+
+/// The root of the store example: provides the state and lays out the parts
+/// that read it.
+#[component]
+pub fn TodoApp() -> Element {
+    use_app_state_provider(State::default);
+
+    rsx! {
+        div { class: if DARK_MODE() { "todos dark" } else { "todos" },
+            ThemeToggle {}
+            TodoInput {}
+            FilterBar {}
+            TodoList {}
+            TodoFooter {}
+        }
+    }
+}
+
+#[component]
+pub fn ThemeToggle() -> Element {
+    rsx! {
+        button { onclick: move |_| *THEME.write() = THEME().toggled(),
+            if DARK_MODE() { "Light mode" } else { "Dark mode" }
+        }
+    }
+}
+
+#[component]
+fn TodoInput() -> Element {
+    let app = use_app_state();
+    let mut title = use_signal(String::new);
+
+    rsx! {
+        form {
+            onsubmit: move |event: FormEvent| {
+                event.prevent_default();
+                app.dispatch(Action::Add(title.take()));
+            },
+            input {
+                placeholder: "What needs doing?",
+                value: "{title}",
+                oninput: move |event| title.set(event.value()),
+            }
+        }
+    }
+}
+
+#[component]
+fn FilterBar() -> Element {
+    let app = use_app_state();
+    let current = use_filter();
+
+    rsx! {
+        nav {
+            for (filter , label) in [(Filter::All, "All"), (Filter::Active, "Active"), (Filter::Done, "Done")] {
+                button {
+                    class: if current() == filter { "selected" },
+                    onclick: move |_| app.dispatch(Action::SetFilter(filter)),
+                    "{label}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TodoList() -> Element {
+    let todos = use_visible_todos();
+
+    rsx! {
+        ul {
+            for todo in todos() {
+                // Props are compared: an item re-renders only when its todo
+                // changed.
+                TodoItem { key: "{todo.id}", todo }
+            }
+        }
+    }
+}
+
+#[component]
+fn TodoItem(todo: Todo) -> Element {
+    let app = use_app_state();
+    let id = todo.id;
+
+    rsx! {
+        li { class: if todo.done { "done" },
+            input {
+                r#type: "checkbox",
+                checked: todo.done,
+                onchange: move |_| app.dispatch(Action::Toggle(id)),
+            }
+            span { "{todo.title}" }
+            button { onclick: move |_| app.dispatch(Action::Remove(id)), "×" }
+        }
+    }
+}
+
+#[component]
+fn TodoFooter() -> Element {
+    let app = use_app_state();
+    let remaining = use_remaining();
+
+    rsx! {
+        footer {
+            span {
+                match remaining() {
+                    1 => "1 item left".to_string(),
+                    count => format!("{count} items left"),
+                }
+            }
+            button { onclick: move |_| app.dispatch(Action::ClearDone), "Clear done" }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Dioxus 0.7 Streaming Template
+//
+// A server function that sends its result a piece at a time, the way a chat
+// model produces tokens or a job reports progress. It returns a `TextStream`
+// with one JSON `Chunk` per line: the network may split or merge what the
+// server writes, so the client buffers text and cuts it at newlines.
+use std::time::Duration;
+
+use dioxus::fullstack::TextStream;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How often the client reconnects after the stream breaks before it gives up.
+const MAX_RETRIES: u32 = 5;
+/// Wait before reconnect attempt `n` is `n * RETRY_DELAY`.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// Position of this piece; the client resumes after the last one it got.
+    pub index: usize,
+    pub total: usize,
+    pub text: String,
+}
+
+/// Streams `text` back word by word, starting at word `from`, so a client
+/// that lost the connection can pick up where it left off.
+#[post("/api/stream/words")]
+pub async fn stream_words(text: String, from: usize) -> Result<TextStream, ServerFnError> {
+    // Pause between words, standing in for real work.
+    const WORD_DELAY: Duration = Duration::from_millis(150);
+
+    let words: Vec<String> = text.split_inclusive(' ').map(str::to_string).collect();
+    let total = words.len();
+
+    Ok(TextStream::spawn(move |tx| async move {
+        for (index, text) in words.into_iter().enumerate().skip(from) {
+            futures_timer::Delay::new(WORD_DELAY).await;
+            let chunk = Chunk { index, total, text };
+            let line = serde_json::to_string(&chunk).expect("a chunk serializes") + "\n";
+            // Sending fails once the client is gone: stop working for nobody.
+            if tx.unbounded_send(line).is_err() {
+                return;
+            }
+        }
+    }))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamStatus {
+    Streaming,
+    /// The connection broke; attempt `attempt` of `MAX_RETRIES` is next.
+    Reconnecting {
+        attempt: u32,
+    },
+    Done,
+    Failed(String),
+}
+
+/// Shows `text` coming back from the server word by word.
+#[component]
+pub fn WordStream(text: ReadSignal<String>) -> Element {
+    let mut output = use_signal(String::new);
+    let mut status = use_signal(|| StreamStatus::Streaming);
+
+    // The task belongs to this component: when it unmounts the task is
+    // dropped, and with it the response, which ends the server's loop.
+    // `use_resource` also restarts it, from scratch, whenever `text` changes.
+    use_resource(move || {
+        let text = text();
+        async move {
+            output.set(String::new());
+            if text.is_empty() {
+                status.set(StreamStatus::Done);
+                return;
+            }
+
+            let mut received = 0;
+            let mut attempt = 0;
+            status.set(StreamStatus::Streaming);
+            loop {
+                let before = received;
+                let Err(err) = receive(&text, &mut received, output).await else {
+                    status.set(StreamStatus::Done);
+                    return;
+                };
+
+                // Only count failures in a row.
+                if received > before {
+                    attempt = 0;
+                }
+                attempt += 1;
+                if attempt > MAX_RETRIES {
+                    status.set(StreamStatus::Failed(err));
+                    return;
+                }
+                status.set(StreamStatus::Reconnecting { attempt });
+                futures_timer::Delay::new(RETRY_DELAY * attempt).await;
+                status.set(StreamStatus::Streaming);
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "word-stream",
+            p { class: "output", "{output}" }
+            match status() {
+                StreamStatus::Streaming => rsx! { span { class: "cursor", "▍" } },
+                StreamStatus::Reconnecting { attempt } => rsx! {
+                    p { class: "warning", "Connection lost, reconnecting ({attempt}/{MAX_RETRIES})..." }
+                },
+                StreamStatus::Done => rsx! {},
+                StreamStatus::Failed(err) => rsx! { p { class: "error", "Stream failed: {err}" } },
+            }
+        }
+    }
+}
+
+/// Reads one connection's worth of chunks into `output`, counting them in
+/// `received`. `Ok` once the last word is in; an error if the request failed
+/// or the stream ended early.
+async fn receive(
+    text: &str,
+    received: &mut usize,
+    mut output: Signal<String>,
+) -> Result<(), String> {
+    let mut stream = stream_words(text.to_string(), *received)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut buffer = String::new();
+    while let Some(piece) = stream.next().await {
+        buffer.push_str(&piece.map_err(|err| err.to_string())?);
+        while let Some(end) = buffer.find('\n') {
+            let line: String = buffer.drain(..=end).collect();
+            let chunk: Chunk = serde_json::from_str(&line).map_err(|err| err.to_string())?;
+
+            output.write().push_str(&chunk.text);
+            *received = chunk.index + 1;
+            if *received == chunk.total {
+                return Ok(());
+            }
+        }
+    }
+    Err("the stream ended early".to_string())
+}
@@ -0,0 +1,142 @@
+// Dioxus 0.7 Streaming SSR Template
+//
+// With out-of-order streaming, which `server::router` turns on with
+// `ServeConfig::enable_out_of_order_streaming()`, the server sends the page
+// as soon as everything outside a waiting `SuspenseBoundary` has rendered:
+// the shell, with each boundary's fallback in its place. Each section follows
+// in the same response once its `use_server_future` resolves, in whatever
+// order they finish, along with its data so the client hydrates from it
+// instead of calling the server again. Without streaming, nothing is sent
+// until the slowest section is done.
+//
+// Give each section its own boundary: one boundary around all of them would
+// hold every section back until the slowest one is done.
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::Skeleton;
+use crate::error::AppError;
+
+/// How long each stand-in data source below takes. Replace their bodies with
+/// your own queries and API calls.
+pub const HEADLINES_DELAY: Duration = Duration::from_millis(50);
+pub const VISITORS_DELAY: Duration = Duration::from_millis(300);
+pub const REPORT_DELAY: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visitors {
+    pub today: u32,
+    pub this_week: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub revenue_cents: u64,
+    pub orders: u32,
+}
+
+/// A quick query.
+#[get("/api/dashboard/headlines")]
+pub async fn headlines() -> Result<Vec<String>, AppError> {
+    tokio::time::sleep(HEADLINES_DELAY).await;
+    Ok(vec![
+        "Version 2.0 is out".to_string(),
+        "New office opens".to_string(),
+    ])
+}
+
+/// A slower query.
+#[get("/api/dashboard/visitors")]
+pub async fn visitors() -> Result<Visitors, AppError> {
+    tokio::time::sleep(VISITORS_DELAY).await;
+    Ok(Visitors {
+        today: 120,
+        this_week: 815,
+    })
+}
+
+/// An expensive aggregate, or a slow third-party API.
+#[get("/api/dashboard/report")]
+pub async fn report() -> Result<Report, AppError> {
+    tokio::time::sleep(REPORT_DELAY).await;
+    Ok(Report {
+        revenue_cents: 1_234_500,
+        orders: 87,
+    })
+}
+
+/// A page of independent sections that each show up as soon as their data
+/// is there. The slowest comes first on the page and still holds back none
+/// of the others.
+#[component]
+pub fn Dashboard() -> Element {
+    rsx! {
+        div { class: "dashboard",
+            h1 { "Dashboard" }
+            Section { title: "Sales", SalesReport {} }
+            Section { title: "Headlines", Headlines {} }
+            Section { title: "Visitors", VisitorCount {} }
+        }
+    }
+}
+
+/// A heading, and the content below it or a skeleton while it loads.
+#[component]
+fn Section(title: String, children: Element) -> Element {
+    rsx! {
+        section { class: "dashboard-section",
+            h2 { "{title}" }
+            SuspenseBoundary {
+                fallback: |_| rsx! { Skeleton { lines: 2 } },
+                {children}
+            }
+        }
+    }
+}
+
+// Each section shows its own error, so one failing source leaves the others
+// in place.
+
+#[component]
+fn Headlines() -> Element {
+    let headlines = use_server_future(headlines)?.suspend()?.cloned();
+
+    match headlines {
+        Ok(headlines) => rsx! {
+            ul {
+                for headline in headlines {
+                    li { "{headline}" }
+                }
+            }
+        },
+        Err(err) => rsx! { p { class: "error", "{err}" } },
+    }
+}
+
+#[component]
+fn VisitorCount() -> Element {
+    let visitors = use_server_future(visitors)?.suspend()?.cloned();
+
+    match visitors {
+        Ok(visitors) => rsx! {
+            p { "{visitors.today} today, {visitors.this_week} this week" }
+        },
+        Err(err) => rsx! { p { class: "error", "{err}" } },
+    }
+}
+
+#[component]
+fn SalesReport() -> Element {
+    let report = use_server_future(report)?.suspend()?.cloned();
+
+    match report {
+        Ok(report) => rsx! {
+            p {
+                "{report.orders} orders, ${report.revenue_cents / 100}.{report.revenue_cents % 100:02}"
+            }
+        },
+        Err(err) => rsx! { p { class: "error", "{err}" } },
+    }
+}
@@ -0,0 +1,320 @@
+// Dioxus 0.7 File Upload Template
+//
+// Server functions that stream each file to disk as it arrives: the request
+// body is never held in memory. `upload` takes a multipart form with several
+// files, `upload_file` one file as the whole body; the file input sends each
+// file with `upload_file`, so every file shows its own progress. Every file
+// is checked against a size and type limit, hashed with SHA-256 on the way,
+// and stored under its hash. The client gets one `Upload` per file back.
+use dioxus::fullstack::{FileStream, MultipartFormData};
+use dioxus::html::FileData;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, FieldError};
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::Extension;
+#[cfg(feature = "server")]
+use futures_util::TryStreamExt;
+
+/// The form field the files are sent in.
+pub const FIELD: &str = "files";
+/// Largest file the server keeps.
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// Most files in one upload.
+pub const MAX_FILES: usize = 10;
+/// Content types the server keeps. The client declares the type, so treat it
+/// as a hint about the file, not a guarantee.
+pub const ALLOWED_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+    "text/plain",
+];
+
+/// A file the server kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredFile {
+    /// The name on the client's disk, for display only.
+    pub name: String,
+    pub content_type: String,
+    pub size: u64,
+    /// Hex SHA-256 of the content, which is also its name on the server.
+    pub sha256: String,
+}
+
+/// What became of one file, in the order they were sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Upload {
+    Stored(StoredFile),
+    Rejected { name: String, reason: String },
+}
+
+/// Stores the files in the form's `files` field. A file over a limit is
+/// rejected on its own and the others are still stored, but more than
+/// `MAX_FILES` files fail the request. Only this route accepts bodies that
+/// large.
+#[post("/api/upload", uploads: Extension<server::UploadDir>)]
+#[middleware(server::body_limit())]
+pub async fn upload(mut form: MultipartFormData) -> Result<Vec<Upload>, AppError> {
+    let mut results = Vec::new();
+    while let Some(mut field) = form.next_field().await.map_err(server::multipart_error)? {
+        // Browsers send an empty part when no file was picked.
+        let name = match field.file_name() {
+            Some(name) if field.name() == Some(FIELD) && !name.is_empty() => name.to_string(),
+            _ => continue,
+        };
+        if results.len() == MAX_FILES {
+            return Err(AppError::validation(
+                FIELD,
+                format!("Upload at most {MAX_FILES} files at once"),
+            ));
+        }
+        let content_type = field.content_type().map(str::to_string);
+        let mut chunks = (&mut field).map_err(server::multipart_error);
+        results.push(server::store(name, content_type, &mut chunks, &uploads.0).await?);
+        // Read past what is left of a rejected file, to get to the next one.
+        server::skip(chunks).await?;
+    }
+    Ok(results)
+}
+
+/// Stores one file sent as the request body. `FileUpload` sends each file
+/// on its own, so each one shows as done as soon as it is.
+#[post("/api/upload/file", uploads: Extension<server::UploadDir>)]
+pub async fn upload_file(file: FileStream) -> Result<Upload, AppError> {
+    let name = file.file_name().to_string();
+    let content_type = file.content_type().map(str::to_string);
+    let mut chunks = file.map_err(AppError::internal);
+    let result = server::store(name, content_type, &mut chunks, &uploads.0).await?;
+    server::skip(chunks).await?;
+    Ok(result)
+}
+
+#[cfg(feature = "server")]
+pub mod server {
+    use std::path::PathBuf;
+
+    use dioxus::server::axum::body::Bytes;
+    use dioxus::server::axum::extract::multipart::MultipartError;
+    use dioxus::server::axum::extract::DefaultBodyLimit;
+    use dioxus::server::axum::http::StatusCode;
+    use futures_util::{Stream, StreamExt, TryStreamExt};
+    use sha2::{Digest, Sha256};
+    use tokio::io::AsyncWriteExt;
+
+    use super::{StoredFile, Upload, ALLOWED_TYPES, FIELD, MAX_FILES, MAX_FILE_SIZE};
+    use crate::error::AppError;
+
+    /// Where stored files go. Add it to the router with
+    /// `.layer(Extension(UploadDir(path)))`.
+    #[derive(Debug, Clone)]
+    pub struct UploadDir(pub PathBuf);
+
+    /// Raises axum's 2 MB request limit to the largest upload allowed, on
+    /// the multipart route only.
+    pub fn body_limit() -> DefaultBodyLimit {
+        // Room for the part headers around the files.
+        const OVERHEAD: u64 = 64 * 1024;
+        DefaultBodyLimit::max((MAX_FILES as u64 * (MAX_FILE_SIZE + OVERHEAD)) as usize)
+    }
+
+    /// Streams one file's `chunks` into the upload directory. Until it is
+    /// complete and within limits the file is a temporary one, deleted on an
+    /// early return. A rejected file is not read to the end.
+    pub async fn store<S>(
+        name: String,
+        content_type: Option<String>,
+        chunks: &mut S,
+        dir: &UploadDir,
+    ) -> Result<Upload, AppError>
+    where
+        S: Stream<Item = Result<Bytes, AppError>> + Unpin,
+    {
+        let dir = &dir.0;
+        let content_type = content_type.unwrap_or_else(|| "application/octet-stream".to_string());
+        if !ALLOWED_TYPES.contains(&content_type.as_str()) {
+            let reason = format!("{content_type} files are not accepted");
+            return Ok(Upload::Rejected { name, reason });
+        }
+
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(AppError::internal)?;
+        let (file, partial) = tempfile::Builder::new()
+            .prefix(".upload-")
+            .tempfile_in(dir)
+            .map_err(AppError::internal)?
+            .into_parts();
+        let mut file = tokio::fs::File::from_std(file);
+
+        let mut hasher = Sha256::new();
+        let mut size = 0;
+        while let Some(chunk) = chunks.try_next().await? {
+            size += chunk.len() as u64;
+            if size > MAX_FILE_SIZE {
+                let reason = format!("Larger than {} MB", MAX_FILE_SIZE / 1024 / 1024);
+                return Ok(Upload::Rejected { name, reason });
+            }
+            hasher.update(&chunk);
+            file.write_all(&chunk).await.map_err(AppError::internal)?;
+        }
+        file.flush().await.map_err(AppError::internal)?;
+
+        // Same content, same name: a second upload replaces the first.
+        let sha256 = format!("{:x}", hasher.finalize());
+        partial
+            .persist(dir.join(&sha256))
+            .map_err(AppError::internal)?;
+
+        Ok(Upload::Stored(StoredFile {
+            name,
+            content_type,
+            size,
+            sha256,
+        }))
+    }
+
+    /// Reads `chunks` to the end, so the client can finish sending before it
+    /// is answered. Past another `MAX_FILE_SIZE` bytes it gives up with
+    /// [`AppError::TooLarge`] instead: the rest is dropped unread, so no body
+    /// keeps the server reading for ever.
+    pub async fn skip<S>(mut chunks: S) -> Result<(), AppError>
+    where
+        S: Stream<Item = Result<Bytes, AppError>> + Unpin,
+    {
+        let mut skipped = 0;
+        while let Some(chunk) = chunks.next().await.transpose()? {
+            skipped += chunk.len() as u64;
+            if skipped > MAX_FILE_SIZE {
+                return Err(AppError::TooLarge);
+            }
+        }
+        Ok(())
+    }
+
+    /// A malformed body, or one over `body_limit`.
+    pub fn multipart_error(err: MultipartError) -> AppError {
+        let message = match err.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => "The upload is too large".to_string(),
+            _ => err.body_text(),
+        };
+        AppError::validation(FIELD, message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FileState {
+    Selected,
+    Uploading,
+    Done(Upload),
+    /// The request failed, e.g. the server could not be reached.
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SelectedFile {
+    name: String,
+    size: u64,
+    state: FileState,
+}
+
+/// A file picker that uploads the picked files one at a time and shows how
+/// each one went.
+#[component]
+pub fn FileUpload() -> Element {
+    let mut picked = use_signal(Vec::<FileData>::new);
+    let mut files = use_signal(Vec::<SelectedFile>::new);
+    let mut error = use_signal(|| None::<AppError>);
+    let uploading = files
+        .read()
+        .iter()
+        .any(|file| file.state == FileState::Uploading);
+
+    let pick = move |event: FormEvent| {
+        error.set(None);
+        let data = event.files();
+        files.set(
+            data.iter()
+                .map(|file| SelectedFile {
+                    name: file.name(),
+                    size: file.size(),
+                    state: FileState::Selected,
+                })
+                .collect(),
+        );
+        picked.set(data);
+    };
+
+    // One request per file: each row goes from selected to uploading to done
+    // on its own, while the next file waits its turn.
+    let submit = move |event: FormEvent| async move {
+        event.prevent_default();
+        error.set(None);
+        if picked.read().len() > MAX_FILES {
+            let message = format!("Upload at most {MAX_FILES} files at once");
+            error.set(Some(AppError::validation(FIELD, message)));
+            return;
+        }
+
+        for (index, data) in picked().into_iter().enumerate() {
+            files.write()[index].state = FileState::Uploading;
+            let state = match upload_file(data.into()).await {
+                Ok(result) => FileState::Done(result),
+                Err(err) => FileState::Failed(err.to_string()),
+            };
+            files.write()[index].state = state;
+        }
+    };
+
+    rsx! {
+        form { class: "upload", onsubmit: submit,
+            input {
+                r#type: "file",
+                name: FIELD,
+                multiple: true,
+                accept: ALLOWED_TYPES.join(","),
+                disabled: uploading,
+                onchange: pick,
+            }
+            FieldError { error: error(), field: FIELD }
+            if let Some(err) = error().filter(|err| err.field_message(FIELD).is_none()) {
+                p { class: "error", "{err}" }
+            }
+            ul {
+                for file in files() {
+                    li { key: "{file.name}",
+                        span { "{file.name} ({human_size(file.size)})" }
+                        match file.state {
+                            FileState::Selected => rsx! {},
+                            FileState::Uploading => rsx! { progress {} },
+                            FileState::Done(Upload::Stored(stored)) => rsx! {
+                                span { class: "stored", title: "{stored.sha256}", "Uploaded" }
+                            },
+                            FileState::Done(Upload::Rejected { reason, .. }) | FileState::Failed(reason) => rsx! {
+                                span { class: "error", "{reason}" }
+                            },
+                        }
+                    }
+                }
+            }
+            button {
+                r#type: "submit",
+                disabled: uploading || files.read().is_empty(),
+                "Upload"
+            }
+        }
+    }
+}
+
+fn human_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}